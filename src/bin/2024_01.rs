@@ -30,7 +30,7 @@ pub fn part_two(input: &str) -> Option<u32> {
     Some(sum)
 }
 
-fn count_occurences(id: &u32, list: &Vec<u32>) -> u32 {
+fn count_occurences(id: &u32, list: &[u32]) -> u32 {
     list.iter().filter(|value| **value == *id).count() as u32
 }
 
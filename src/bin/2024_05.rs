@@ -22,7 +22,7 @@ struct Data {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let data = parse_input(&input);
+    let data = parse_input(input);
 
     Some(
         data.updates
@@ -34,7 +34,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let data = parse_input(&input);
+    let data = parse_input(input);
 
     Some(
         data.updates
@@ -1,4 +1,4 @@
-use advent_of_code::{Direction, Grid, Vec2};
+use advent_of_code::{span, Direction, Grid, Vec2};
 use std::collections::HashSet;
 
 advent_of_code::solution!(6);
@@ -39,7 +39,8 @@ impl Guard {
                         visited_positions: HashSet::new(),
                     };
 
-                    let is_in_a_loop = new_guard.detect_loop(&grid_with_obstacle);
+                    let is_in_a_loop =
+                        span!("loop check", new_guard.detect_loop(&grid_with_obstacle));
                     if is_in_a_loop {
                         placed_obstacles.insert(obstacle_pos);
                     }
@@ -76,11 +77,7 @@ impl Guard {
     }
 
     fn run_simulation_step(&mut self, grid: &Grid) -> Option<Vec2> {
-        let next_position = match self.get_next_position(grid) {
-            Some(pos) => pos,
-            // out of grid
-            None => return None,
-        };
+        let next_position = self.get_next_position(grid)?;
 
         match grid.char_at(&next_position) {
             '.' | '^' => {
@@ -116,7 +113,7 @@ impl Guard {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let grid = Grid::from_input(input);
+    let grid = span!("parse", Grid::from_input(input));
 
     let guard_starting_position = grid.find_first_char_position('^').unwrap();
     let mut guard = Guard {
@@ -125,14 +122,14 @@ pub fn part_one(input: &str) -> Option<u32> {
         visited_positions: HashSet::new(),
     };
 
-    guard.simulate(&grid);
+    span!("walk", guard.simulate(&grid));
     let unique_visited_positions: HashSet<Vec2> =
         guard.visited_positions.iter().map(|p| p.0).collect();
     Some(unique_visited_positions.len() as u32)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let grid = Grid::from_input(input);
+    let grid = span!("parse", Grid::from_input(input));
 
     let guard_starting_position = grid.find_first_char_position('^').unwrap();
     let mut guard = Guard {
@@ -141,6 +138,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         visited_positions: HashSet::new(),
     };
 
+    let _walk = span!("walk");
     Some(guard.find_number_of_new_obstacle_positions_that_cause_a_loop(&grid))
 }
 
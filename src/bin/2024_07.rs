@@ -13,11 +13,7 @@ impl Equation {
         // base case -> if there is only one element left on rhs it must equal the lhs
         let last_element = self.rhs.last().unwrap();
         if self.rhs.len() == 1 {
-            return if *last_element == self.lhs {
-                true
-            } else {
-                false
-            };
+            return *last_element == self.lhs;
         };
 
         if extended_operator_set {
@@ -45,7 +41,7 @@ impl Equation {
         }
 
         // Check what happens if the last operation is division
-        let is_lhs_divisible_by_last_element = self.lhs % last_element == 0;
+        let is_lhs_divisible_by_last_element = self.lhs.is_multiple_of(*last_element);
         if is_lhs_divisible_by_last_element {
             let mut new_rhs = self.rhs.clone();
             new_rhs.pop();
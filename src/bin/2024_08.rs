@@ -37,7 +37,7 @@ fn get_antinodes(satellite: &Vec2, other_satellites: Vec<&Vec2>, grid: &Grid) ->
     other_satellites
         .iter()
         .filter_map(|other| {
-            let distance = satellite.sub(&other);
+            let distance = satellite.sub(other);
             let antinode = satellite.add(&distance);
 
             if grid.is_point_in_gird(&antinode) {
@@ -58,7 +58,7 @@ fn get_antinodes_with_resonant_harmonics(
         .iter()
         .flat_map(|other| {
             let mut antinodes = HashSet::new();
-            let distance = satellite.sub(&other);
+            let distance = satellite.sub(other);
 
             let mut antinode = satellite.add(&distance);
             while grid.is_point_in_gird(&antinode) {
@@ -76,9 +76,7 @@ fn parse_satellite_groups(grid: &Grid) -> Vec<HashSet<Vec2>> {
 
     for (pos, value) in grid.iter() {
         if value != '.' {
-            map.entry(value)
-                .or_insert_with(|| HashSet::new())
-                .insert(pos);
+            map.entry(value).or_insert_with(HashSet::new).insert(pos);
         }
     }
 
@@ -10,7 +10,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     let trailheads = grid.find_char_positions('0');
     let paths_count = trailheads
         .iter()
-        .map(|head| make_paths(head, &grid, false).iter().count() as u32)
+        .map(|head| make_paths(head, &grid, false).len() as u32)
         .sum();
 
     Some(paths_count)
@@ -22,7 +22,7 @@ pub fn part_two(input: &str) -> Option<u32> {
     let trailheads = grid.find_char_positions('0');
     let paths_count = trailheads
         .iter()
-        .map(|head| make_paths(head, &grid, true).iter().count() as u32)
+        .map(|head| make_paths(head, &grid, true).len() as u32)
         .sum();
 
     Some(paths_count)
@@ -57,7 +57,7 @@ fn make_paths(head: &Vec2, grid: &Grid, find_all_paths: bool) -> Vec<Path> {
             let next_position = current_position.add(&direction.get_offset());
             if !visited_positions.contains(&next_position) && grid.is_point_in_gird(&next_position)
             {
-                let current_height = grid.char_at(&current_position).to_digit(10).unwrap() as i32;
+                let current_height = grid.char_at(current_position).to_digit(10).unwrap() as i32;
                 let next_height = grid.char_at(&next_position).to_digit(10).unwrap() as i32;
 
                 // only consider positions with difference of 1 in height
@@ -61,7 +61,7 @@ impl Stones {
 }
 
 fn has_even_number_of_digits(value: u64) -> bool {
-    value.to_string().len() % 2 == 0
+    value.to_string().len().is_multiple_of(2)
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
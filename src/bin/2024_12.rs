@@ -50,7 +50,7 @@ impl Region {
             let offset_x = Vec2::new(1, 0);
             let mut expected_next_fence = (
                 possible_side_starting_fence_pos.add(&offset_x),
-                fence_direction.clone(),
+                fence_direction,
             );
 
             while fences.contains(&expected_next_fence) {
@@ -66,7 +66,7 @@ impl Region {
             let offset_y = Vec2::new(0, 1);
             let mut expected_next_fence = (
                 possible_side_starting_fence_pos.add(&offset_y),
-                fence_direction.clone(),
+                fence_direction,
             );
 
             while fences.contains(&expected_next_fence) {
@@ -114,8 +114,7 @@ fn parse_regions(grid: &Grid) -> Vec<Region> {
 
             queue.push(positon);
 
-            while !queue.is_empty() {
-                let current_position = queue.pop().unwrap();
+            while let Some(current_position) = queue.pop() {
                 if visited_positions.contains(&current_position) {
                     continue;
                 }
@@ -68,7 +68,7 @@ impl Robot {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let mut robots: Vec<Robot> = input.lines().map(|l| Robot::from_line(l)).collect();
+    let mut robots: Vec<Robot> = input.lines().map(Robot::from_line).collect();
 
     let grid_size = Vec2::new(101, 103);
     for _ in 0..100 {
@@ -103,7 +103,7 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut robots: Vec<Robot> = input.lines().map(|l| Robot::from_line(l)).collect();
+    let mut robots: Vec<Robot> = input.lines().map(Robot::from_line).collect();
 
     let grid_size = Vec2::new(101, 103);
     for i in 0..10000 {
@@ -26,12 +26,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         }
     }
 
-    Some(
-        grid.find_char_positions('O')
-            .iter()
-            .map(|b| distance(b))
-            .sum(),
-    )
+    Some(grid.find_char_positions('O').iter().map(distance).sum())
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
@@ -50,7 +45,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         if next_cell == '[' || next_cell == ']' {
             match m {
                 Direction::Right => {
-                    if let Some(empty_cell_pos) = try_find_empty_cell(&robot_pos, &m, &grid) {
+                    if let Some(empty_cell_pos) = try_find_empty_cell(&robot_pos, m, &grid) {
                         for x in (robot_pos.x..empty_cell_pos.x).rev() {
                             let p = Vec2::new(x, robot_pos.y);
                             let p2 = Vec2::new(x + 1, robot_pos.y);
@@ -60,7 +55,7 @@ pub fn part_two(input: &str) -> Option<u32> {
                     }
                 }
                 Direction::Left => {
-                    if let Some(empty_cell_pos) = try_find_empty_cell(&robot_pos, &m, &grid) {
+                    if let Some(empty_cell_pos) = try_find_empty_cell(&robot_pos, m, &grid) {
                         for x in empty_cell_pos.x..robot_pos.x {
                             let p = Vec2::new(x, robot_pos.y);
                             let p2 = Vec2::new(x + 1, robot_pos.y);
@@ -70,7 +65,7 @@ pub fn part_two(input: &str) -> Option<u32> {
                     }
                 }
                 Direction::Up => {
-                    let boxes_to_move = find_boxes_to_move_vertically(&next_robot_pos, &m, &grid);
+                    let boxes_to_move = find_boxes_to_move_vertically(&next_robot_pos, m, &grid);
 
                     let mut can_move = true;
                     for (l, r) in &boxes_to_move {
@@ -88,7 +83,9 @@ pub fn part_two(input: &str) -> Option<u32> {
 
                     if can_move {
                         for (l, r) in boxes_to_move.iter().sorted_by_key(|b| b.0.y) {
-                            if grid.char_at(&l.add(&Direction::Up.get_offset())) == '#' || grid.char_at(&r.add(&Direction::Up.get_offset()))== '#' {
+                            if grid.char_at(&l.add(&Direction::Up.get_offset())) == '#'
+                                || grid.char_at(&r.add(&Direction::Up.get_offset())) == '#'
+                            {
                                 panic!("moved a wall");
                             }
                             grid.swap_cells(l, &l.add(&Direction::Up.get_offset()));
@@ -100,7 +97,7 @@ pub fn part_two(input: &str) -> Option<u32> {
                     }
                 }
                 Direction::Down => {
-                    let boxes_to_move = find_boxes_to_move_vertically(&next_robot_pos, &m, &grid);
+                    let boxes_to_move = find_boxes_to_move_vertically(&next_robot_pos, m, &grid);
 
                     let mut can_move = true;
                     for (l, r) in &boxes_to_move {
@@ -118,7 +115,9 @@ pub fn part_two(input: &str) -> Option<u32> {
 
                     if can_move {
                         for (l, r) in boxes_to_move.iter().sorted_by_key(|b| b.0.y).rev() {
-                            if grid.char_at(&l.add(&Direction::Down.get_offset())) == '#' || grid.char_at(&r.add(&Direction::Down.get_offset()))== '#' {
+                            if grid.char_at(&l.add(&Direction::Down.get_offset())) == '#'
+                                || grid.char_at(&r.add(&Direction::Down.get_offset())) == '#'
+                            {
                                 grid.pretty_print();
                                 panic!("moved a wall");
                             }
@@ -135,12 +134,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         }
     }
 
-    Some(
-        grid.find_char_positions('[')
-            .iter()
-            .map(|b| distance(b))
-            .sum(),
-    )
+    Some(grid.find_char_positions('[').iter().map(distance).sum())
 }
 
 fn find_boxes_to_move_vertically(
@@ -148,11 +142,11 @@ fn find_boxes_to_move_vertically(
     dir: &Direction,
     grid: &Grid,
 ) -> Vec<(Vec2, Vec2)> {
-    if grid.char_at(&current_pos) != '[' && grid.char_at(&current_pos) != ']' {
+    if grid.char_at(current_pos) != '[' && grid.char_at(current_pos) != ']' {
         return vec![];
     }
 
-    let is_left_part_of_box = grid.char_at(&current_pos) == '[';
+    let is_left_part_of_box = grid.char_at(current_pos) == '[';
 
     let left_part_of_box_pos = if is_left_part_of_box {
         *current_pos
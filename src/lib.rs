@@ -140,7 +140,7 @@ impl Grid {
         None
     }
 
-    pub fn iter(&self) -> GridIter {
+    pub fn iter(&self) -> GridIter<'_> {
         GridIter {
             grid: self,
             current_x: 0,
@@ -1,79 +1,238 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, leaderboard, read, report, scaffold, solve, status, time, verify,
+};
+use advent_of_code::template::print_history;
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::PuzzleId;
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::{
+        commands::{examples::ExamplesOptions, solve::SolveOptions, time::TimeOptions},
+        config,
+        report::ReportFormat,
+        Day, DayFromStrError, PuzzleId,
+    };
+    use std::{process, time::Duration};
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
+            force: bool,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+            refresh: bool,
+        },
+        Examples {
+            puzzle: PuzzleId,
+            options: ExamplesOptions,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
             overwrite: bool,
         },
         Solve {
-            day: Day,
-            release: bool,
-            dhat: bool,
-            submit: Option<u8>,
+            puzzle: PuzzleId,
+            options: SolveOptions,
         },
         All {
+            year: u16,
             release: bool,
+            jobs: usize,
+            timeout: Option<Duration>,
         },
         Time {
-            all: bool,
+            year: u16,
+            day: Option<Day>,
+            options: TimeOptions,
+        },
+        History {
+            puzzle: PuzzleId,
+        },
+        Report {
+            year: u16,
+            format: ReportFormat,
+            output: Option<String>,
+        },
+        Status {
+            year: u16,
+            test: bool,
+        },
+        Leaderboard {
+            year: u16,
+            id: Option<String>,
+            file: Option<String>,
+            day: Option<Day>,
+        },
+        Verify {
+            year: u16,
             day: Option<Day>,
-            store: bool,
+            jobs: usize,
+            timeout: Option<Duration>,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// Parse the `--timeout <seconds>` option shared by commands that run solutions.
+    fn parse_timeout(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Duration>, pico_args::Error> {
+        args.opt_value_from_fn("--timeout", |s| {
+            s.parse::<f64>()
+                .map_err(|e| e.to_string())
+                .and_then(|x| Duration::try_from_secs_f64(x).map_err(|e| e.to_string()))
+        })
+    }
+
+    /// Parse the day argument, which has to be a day of the event of `year`.
+    /// Parse the puzzle of a day, which has to be a day of the event of `year`.
+    fn parse_puzzle(
+        args: &mut pico_args::Arguments,
+        year: u16,
+    ) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        let day: String = args.free_from_str()?;
+        Ok(PuzzleId::parse(year, &day)?)
+    }
+
+    /// Parse an optional day, which has to be a day of the event of `year`.
+    fn parse_opt_day(year: u16, day: Option<String>) -> Result<Option<Day>, DayFromStrError> {
+        day.map(|day| Day::parse_for_year(year, &day)).transpose()
+    }
+
+    /// Parse the `--year <year>` option shared by all commands, defaults to the configured year.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<u16, Box<dyn std::error::Error>> {
+        if let Some(year) = args.opt_value_from_str("--year")? {
+            return Ok(year);
+        }
+
+        config::get()
+            .year
+            .ok_or_else(|| "no year set. Pass --year <year> or set `year` in \"aoc.toml\".".into())
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
-        let mut args = pico_args::Arguments::from_env();
+        parse_from(pico_args::Arguments::from_env())
+    }
 
-        let app_args = match args.subcommand()?.as_deref() {
+    fn parse_from(
+        mut args: pico_args::Arguments,
+    ) -> Result<AppArguments, Box<dyn std::error::Error>> {
+        let subcommand = args.subcommand()?;
+        let year = parse_year(&mut args)?;
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
+                year,
                 release: args.contains("--release"),
+                jobs: args.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1),
+                timeout: parse_timeout(&mut args)?,
+            },
+            Some("time") if args.contains("--history") => AppArguments::History {
+                puzzle: parse_puzzle(&mut args, year)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let timeout = parse_timeout(&mut args)?;
+                let spans = args.contains("--spans");
+                let threshold = args.opt_value_from_str("--threshold")?;
+                let compare = args
+                    .contains("--compare")
+                    .then(|| threshold.unwrap_or(config::get().compare_threshold));
 
-                AppArguments::Time {
+                let options = TimeOptions {
                     all,
-                    day: args.opt_free_from_str()?,
                     store,
+                    timeout,
+                    compare_threshold: compare,
+                    count_allocs: args.contains("--count-allocs"),
+                    spans,
+                };
+
+                AppArguments::Time {
+                    year,
+                    day: parse_opt_day(year, args.opt_free_from_str()?)?,
+                    options,
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args, year)?,
+                force: args.contains("--force"),
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args, year)?,
+                refresh: args.contains("--refresh"),
+            },
+            Some("examples") => AppArguments::Examples {
+                puzzle: parse_puzzle(&mut args, year)?,
+                options: ExamplesOptions {
+                    block: args.opt_value_from_str("--block")?,
+                    answer: args.opt_value_from_str("--answer")?,
+                    part: args.opt_value_from_str("--part")?,
+                    force: args.contains("--force"),
+                },
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args, year)?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+                puzzle: parse_puzzle(&mut args, year)?,
+                options: SolveOptions {
+                    release: args.contains("--release"),
+                    submit_part: args.opt_value_from_str("--submit")?,
+                    dhat: args.contains("--dhat"),
+                    timeout: parse_timeout(&mut args)?,
+                    count_allocs: args.contains("--count-allocs"),
+                    spans: args.contains("--spans"),
+                    all_inputs: args.contains("--all-inputs"),
+                    input: args.opt_value_from_str("--input")?,
+                    example: args.contains("--example"),
+                    part: args.opt_value_from_str("--part")?,
+                    watch: args.contains("--watch"),
+                    test: args.contains("--test"),
+                },
+            },
+            Some("export") => AppArguments::Report {
+                year,
+                format: args
+                    .opt_value_from_str("--format")?
+                    .unwrap_or(ReportFormat::Json),
+                output: args.opt_value_from_str("--output")?,
             },
+            Some("status") => AppArguments::Status {
+                year,
+                test: args.contains("--test"),
+            },
+            Some("leaderboard") => {
+                let file = args.opt_value_from_str("--file")?;
+                let day = parse_opt_day(year, args.opt_value_from_str("--day")?)?;
+
+                AppArguments::Leaderboard {
+                    year,
+                    id: args.opt_free_from_str()?,
+                    file,
+                    day,
+                }
+            }
+            Some("verify") => {
+                let jobs = args.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1);
+                let timeout = parse_timeout(&mut args)?;
+
+                AppArguments::Verify {
+                    year,
+                    day: parse_opt_day(year, args.opt_free_from_str()?)?,
+                    jobs,
+                    timeout,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -93,6 +252,31 @@ mod args {
 
         Ok(app_args)
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::{parse_from, AppArguments};
+
+        fn parse_args(args: &[&str]) -> Result<AppArguments, Box<dyn std::error::Error>> {
+            parse_from(pico_args::Arguments::from_vec(
+                args.iter().map(Into::into).collect(),
+            ))
+        }
+
+        #[test]
+        fn rejects_days_outside_of_the_event() {
+            assert!(parse_args(&["scaffold", "13", "--year", "2025"]).is_err());
+            assert!(parse_args(&["solve", "13", "--year", "2025"]).is_err());
+            assert!(parse_args(&["time", "13", "--year", "2025"]).is_err());
+
+            let Ok(AppArguments::Scaffold { puzzle, .. }) =
+                parse_args(&["scaffold", "13", "--year", "2024"])
+            else {
+                panic!("expected day 13 of 2024 to be valid");
+            };
+            assert_eq!(puzzle.to_string(), "2024 day 13");
+        }
+    }
 }
 
 fn main() {
@@ -102,38 +286,58 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All {
+                year,
+                release,
+                jobs,
+                timeout,
+            } => all::handle(year, release, jobs, timeout),
+            AppArguments::Time { year, day, options } => time::handle(year, day, &options),
+            AppArguments::History { puzzle } => print_history(puzzle),
+            AppArguments::Download { puzzle, force } => download::handle(puzzle, force),
+            AppArguments::Read { puzzle, refresh } => read::handle(puzzle, refresh),
+            AppArguments::Examples { puzzle, options } => examples::handle(puzzle, &options),
             AppArguments::Scaffold {
-                day,
+                puzzle,
                 download,
                 overwrite,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(puzzle, overwrite);
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle, false);
                 }
             }
-            AppArguments::Solve {
+            AppArguments::Solve { puzzle, options } => solve::handle(puzzle, &options),
+            AppArguments::Report {
+                year,
+                format,
+                output,
+            } => report::handle(year, format, output),
+            AppArguments::Status { year, test } => status::handle(year, test),
+            AppArguments::Leaderboard {
+                year,
+                id,
+                file,
                 day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
+            } => leaderboard::handle(year, id, file, day),
+            AppArguments::Verify {
+                year,
+                day,
+                jobs,
+                timeout,
+            } => verify::handle(year, day, jobs, timeout),
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                match PuzzleId::today() {
+                    Some(puzzle) => {
+                        scaffold::handle(puzzle, false);
+                        download::handle(puzzle, false);
+                        read::handle(puzzle, false)
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run on a day of this year's event \
+                            in december. Please use `scaffold` with a specific day."
                         );
                         process::exit(1)
                     }
@@ -0,0 +1,169 @@
+/// Module that stores known answers for real puzzle inputs.
+/// Answers live in `data/{year}/answers/{day}-{part}.txt`, they are written after an accepted submission or by hand.
+/// Answers for named inputs live in `data/{year}/answers/{day}/{name}-{part}.txt`,
+/// expected answers for examples in `data/{year}/answers/examples/{day}-{part}.txt`.
+use std::{fs, io, path::PathBuf};
+
+use crate::template::{
+    inputs::{self, InputKind},
+    PuzzleId,
+};
+
+static ANSWERS_DIR: &str = "answers";
+
+/// The result of comparing an answer against the known answer for a part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnswerCheck {
+    /// No answer is known for the part.
+    Unknown,
+    Correct,
+    Incorrect {
+        expected: String,
+    },
+}
+
+/// Path of the known answer for a part. `input` is the name of a named input, `None` for the default input.
+#[must_use]
+pub fn get_answer_path(puzzle: PuzzleId, input: Option<&str>, part: u8) -> PathBuf {
+    let day = puzzle.day;
+    match input {
+        Some(input) => answers_dir(puzzle)
+            .join(day.to_string())
+            .join(format!("{input}-{part}.txt")),
+        None => answers_dir(puzzle).join(format!("{day}-{part}.txt")),
+    }
+}
+
+/// Path of the expected answer for a part when run against its example.
+#[must_use]
+pub fn get_example_answer_path(puzzle: PuzzleId, part: u8) -> PathBuf {
+    answers_dir(puzzle)
+        .join("examples")
+        .join(format!("{}-{part}.txt", puzzle.day))
+}
+
+fn answers_dir(puzzle: PuzzleId) -> PathBuf {
+    puzzle.data_dir().join(ANSWERS_DIR)
+}
+
+/// Read the known answer for a part. Trailing whitespace is ignored so answers can be edited by hand.
+pub fn read_answer(puzzle: PuzzleId, input: Option<&str>, part: u8) -> Option<String> {
+    read_answer_file(get_answer_path(puzzle, input, part))
+}
+
+/// Read the expected answer for a part when run against its example.
+pub fn read_example_answer(puzzle: PuzzleId, part: u8) -> Option<String> {
+    read_answer_file(get_example_answer_path(puzzle, part))
+}
+
+fn read_answer_file(path: PathBuf) -> Option<String> {
+    let answer = fs::read_to_string(path).ok()?;
+    let answer = answer.trim_end();
+
+    if answer.is_empty() {
+        None
+    } else {
+        Some(answer.to_string())
+    }
+}
+
+/// Store the known answer for a part of the default input, overwriting a previously stored answer.
+pub fn store_answer(puzzle: PuzzleId, part: u8, answer: &str) -> Result<(), io::Error> {
+    fs::create_dir_all(answers_dir(puzzle))?;
+    fs::write(
+        get_answer_path(puzzle, None, part),
+        format!("{}\n", answer.trim_end()),
+    )
+}
+
+/// Store the expected answer for a part when run against its example.
+pub fn store_example_answer(puzzle: PuzzleId, part: u8, answer: &str) -> Result<(), io::Error> {
+    let path = get_example_answer_path(puzzle, part);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, format!("{}\n", answer.trim_end()))
+}
+
+/// Returns `true` if an answer is known for at least one part of any input of the day.
+pub fn has_answers(puzzle: PuzzleId) -> bool {
+    let names = inputs::list_named_inputs(puzzle)
+        .into_iter()
+        .map(|i| i.name().map(String::from));
+
+    [None].into_iter().chain(names).any(|name| {
+        [1, 2]
+            .iter()
+            .any(|part| read_answer(puzzle, name.as_deref(), *part).is_some())
+    })
+}
+
+/// Compare an answer against the known answer of the input it was computed for.
+pub fn check_answer(puzzle: PuzzleId, input: &InputKind, part: u8, answer: &str) -> AnswerCheck {
+    let known = match input {
+        InputKind::Default => read_answer(puzzle, None, part),
+        InputKind::Named(name) => read_answer(puzzle, Some(name), part),
+        InputKind::Example => read_example_answer(puzzle, part),
+        InputKind::Custom => None,
+    };
+
+    match known {
+        None => AnswerCheck::Unknown,
+        Some(expected) => compare(&expected, answer),
+    }
+}
+
+fn compare(expected: &str, answer: &str) -> AnswerCheck {
+    if expected == answer.trim_end() {
+        AnswerCheck::Correct
+    } else {
+        AnswerCheck::Incorrect {
+            expected: expected.to_string(),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{compare, get_answer_path, get_example_answer_path, AnswerCheck};
+    use crate::{day, template::PuzzleId};
+
+    #[test]
+    fn builds_answer_paths() {
+        let puzzle = PuzzleId::new(2024, day!(6));
+        assert_eq!(
+            get_answer_path(puzzle, None, 1),
+            PathBuf::from("data/2024/answers/06-1.txt")
+        );
+        assert_eq!(
+            get_answer_path(puzzle, Some("alice"), 2),
+            PathBuf::from("data/2024/answers/06/alice-2.txt")
+        );
+        assert_eq!(
+            get_example_answer_path(puzzle, 2),
+            PathBuf::from("data/2024/answers/examples/06-2.txt")
+        );
+    }
+
+    #[test]
+    fn matches_equal_answers() {
+        assert_eq!(compare("42", "42"), AnswerCheck::Correct);
+    }
+
+    #[test]
+    fn ignores_trailing_whitespace() {
+        assert_eq!(compare("a\nb", "a\nb\n"), AnswerCheck::Correct);
+    }
+
+    #[test]
+    fn detects_mismatches() {
+        assert_eq!(
+            compare("42", "41"),
+            AnswerCheck::Incorrect {
+                expected: "42".into()
+            }
+        );
+    }
+}
@@ -0,0 +1,536 @@
+/// Client for the Advent of Code website.
+///
+/// The session token is read from the `AOC_SESSION` (or `ADVENT_OF_CODE_SESSION`) environment variable,
+/// falling back to the session file set in `aoc.toml`, or `~/.adventofcode.session` and
+/// `~/.config/adventofcode.session` if none is set.
+/// Requests go to `https://adventofcode.com` unless `AOC_BASE_URL` points to another server, e.g. a local stand-in.
+/// Requests are throttled, the time of the last request is persisted so the throttle applies across commands.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::{config, puzzle, PuzzleId};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+const SESSION_ENV_VARS: [&str; 2] = ["AOC_SESSION", "ADVENT_OF_CODE_SESSION"];
+const SESSION_FILE_NAMES: [&str; 2] = [".adventofcode.session", ".config/adventofcode.session"];
+const TIMEOUT: Duration = Duration::from_secs(30);
+/// Minimum time between two requests to the site.
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(2);
+/// Stored in the data directory, shared by all years.
+static LAST_REQUEST_FILE_NAME: &str = ".last_request";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    /// No session token was found in the environment or the session file.
+    SessionNotFound,
+    /// The server responded with an error status.
+    Status(u16),
+    /// The request did not reach the server.
+    Transport(String),
+    /// The puzzle page did not contain a puzzle description.
+    PuzzleNotFound,
+    /// The response to a submission did not contain a known verdict.
+    UnexpectedResponse,
+    Io(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session token found. Set AOC_SESSION or create the file \"~/.adventofcode.session\"."
+            ),
+            AocClientError::Status(400) => write!(
+                f,
+                "the server responded with status 400, check that your session token is valid."
+            ),
+            AocClientError::Status(404) => write!(
+                f,
+                "the server responded with status 404, the puzzle might not be unlocked yet."
+            ),
+            AocClientError::Status(status) => {
+                write!(f, "the server responded with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::PuzzleNotFound => {
+                write!(f, "the puzzle page does not contain a puzzle description.")
+            }
+            AocClientError::UnexpectedResponse => {
+                write!(f, "the server responded with an unknown verdict.")
+            }
+            AocClientError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::Io(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, _) => AocClientError::Status(status),
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/// The verdict of the server on a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// The answer is wrong, without a hint whether it is too high or too low.
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The part was already solved, the answer was not checked.
+    AlreadySolved,
+    /// An answer was submitted too recently, holds the time left to wait if the server sent it.
+    RateLimited(Option<Duration>),
+}
+
+impl Verdict {
+    /// Parse the verdict from the response page of a submission.
+    #[must_use]
+    pub fn parse(html: &str) -> Option<Self> {
+        if html.contains("That's the right answer") {
+            Some(Verdict::Correct)
+        } else if html.contains("You don't seem to be solving the right level") {
+            Some(Verdict::AlreadySolved)
+        } else if html.contains("You gave an answer too recently") {
+            Some(Verdict::RateLimited(parse_wait(html)))
+        } else if html.contains("That's not the right answer") {
+            Some(if html.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if html.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Wrong
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the server rejected the answer.
+    #[must_use]
+    pub fn is_wrong(&self) -> bool {
+        matches!(self, Verdict::Wrong | Verdict::TooHigh | Verdict::TooLow)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer!"),
+            Verdict::Wrong => write!(f, "That's not the right answer."),
+            Verdict::TooHigh => write!(f, "That's not the right answer, it is too high."),
+            Verdict::TooLow => write!(f, "That's not the right answer, it is too low."),
+            Verdict::AlreadySolved => write!(f, "This part is already solved."),
+            Verdict::RateLimited(Some(wait)) => {
+                write!(f, "An answer was submitted too recently, wait {wait:?}.")
+            }
+            Verdict::RateLimited(None) => write!(f, "An answer was submitted too recently."),
+        }
+    }
+}
+
+/// Parse the time left to wait from a message like "You have 1m 23s left to wait.".
+fn parse_wait(html: &str) -> Option<Duration> {
+    let start = html.find("You have ")? + "You have ".len();
+    let end = start + html[start..].find(" left to wait")?;
+
+    html[start..end]
+        .split_whitespace()
+        .try_fold(0, |secs, token| {
+            let (value, unit) = token.split_at(token.len().checked_sub(1)?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "h" => Some(secs + value * 3600),
+                "m" => Some(secs + value * 60),
+                "s" => Some(secs + value),
+                _ => None,
+            }
+        })
+        .map(Duration::from_secs)
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: Option<String>,
+    agent: ureq::Agent,
+    /// File the time of the last request is persisted in, `None` disables the throttle.
+    throttle_file: Option<PathBuf>,
+}
+
+impl AocClient {
+    #[must_use]
+    pub fn new(base_url: &str, session: Option<String>) -> Self {
+        AocClient {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session,
+            agent: build_agent(USER_AGENT),
+            throttle_file: None,
+        }
+    }
+
+    /// Identify requests with a custom User-Agent, e.g. one that includes your contact details.
+    #[must_use]
+    pub fn with_user_agent(self, user_agent: &str) -> Self {
+        AocClient {
+            agent: build_agent(user_agent),
+            ..self
+        }
+    }
+
+    /// Throttle requests, persisting the time of the last request to `path`.
+    #[must_use]
+    pub fn with_throttle(self, path: impl Into<PathBuf>) -> Self {
+        AocClient {
+            throttle_file: Some(path.into()),
+            ..self
+        }
+    }
+
+    /// Create a client configured by the environment, see the module documentation.
+    #[must_use]
+    pub fn from_env() -> Self {
+        let config = config::get();
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let user_agent = config.user_agent.as_deref().unwrap_or(USER_AGENT);
+
+        AocClient::new(&base_url, read_session(config.session_file.as_deref()))
+            .with_user_agent(user_agent)
+            .with_throttle(config.data_dir.join(LAST_REQUEST_FILE_NAME))
+    }
+
+    /// Fetch the puzzle input of a day, requires a session token.
+    pub fn get_input(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        if self.session.is_none() {
+            return Err(AocClientError::SessionNotFound);
+        }
+
+        self.get(&format!(
+            "{}/day/{}/input",
+            puzzle.year,
+            puzzle.day.into_inner()
+        ))
+    }
+
+    /// Fetch the puzzle description of a day as markdown.
+    /// Without a session token, only the description of part one is available.
+    pub fn get_puzzle(&self, puzzle: PuzzleId) -> Result<String, AocClientError> {
+        let html = self.get(&format!("{}/day/{}", puzzle.year, puzzle.day.into_inner()))?;
+        puzzle::html_to_markdown(&html).ok_or(AocClientError::PuzzleNotFound)
+    }
+
+    /// Fetch the JSON data of a private leaderboard of an event, requires a session token.
+    pub fn get_leaderboard(&self, year: u16, id: &str) -> Result<String, AocClientError> {
+        if self.session.is_none() {
+            return Err(AocClientError::SessionNotFound);
+        }
+
+        self.get(&format!("{year}/leaderboard/private/view/{id}.json"))
+    }
+
+    /// Submit the answer to a part of a day, requires a session token.
+    pub fn submit_answer(
+        &self,
+        puzzle: PuzzleId,
+        part: u8,
+        answer: &str,
+    ) -> Result<Verdict, AocClientError> {
+        let Some(session) = &self.session else {
+            return Err(AocClientError::SessionNotFound);
+        };
+
+        self.throttle();
+
+        let url = format!(
+            "{}/{}/day/{}/answer",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        );
+        let html = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={session}"))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+
+        Verdict::parse(&html).ok_or(AocClientError::UnexpectedResponse)
+    }
+
+    fn get(&self, path: &str) -> Result<String, AocClientError> {
+        let mut request = self.agent.get(&format!("{}/{path}", self.base_url));
+        self.throttle();
+
+        if let Some(session) = &self.session {
+            request = request.set("Cookie", &format!("session={session}"));
+        }
+
+        Ok(request.call()?.into_string()?)
+    }
+
+    /// Wait until the minimum interval since the last request passed, then record this request.
+    fn throttle(&self) {
+        let Some(path) = &self.throttle_file else {
+            return;
+        };
+
+        let last_request = fs::read_to_string(path)
+            .ok()
+            .and_then(|millis| millis.trim().parse().ok());
+
+        let wait = wait_time(last_request, unix_millis());
+        if !wait.is_zero() {
+            println!("Waiting {wait:.1?} before the next request...");
+            thread::sleep(wait);
+        }
+
+        if let Err(e) = fs::write(path, unix_millis().to_string()) {
+            eprintln!("Failed to store the time of the last request: {e}");
+        }
+    }
+}
+
+fn build_agent(user_agent: &str) -> ureq::Agent {
+    ureq::AgentBuilder::new()
+        .timeout(TIMEOUT)
+        .user_agent(user_agent)
+        .build()
+}
+
+fn unix_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis())
+}
+
+/// Time left to wait before the next request, given the unix time in milliseconds of the last one.
+fn wait_time(last_request: Option<u128>, now: u128) -> Duration {
+    let Some(elapsed) = last_request.map(|last| now.saturating_sub(last)) else {
+        return Duration::ZERO;
+    };
+
+    #[allow(clippy::cast_possible_truncation)]
+    MIN_REQUEST_INTERVAL.saturating_sub(Duration::from_millis(elapsed as u64))
+}
+
+/// Read the session token from the environment or the session file.
+/// Without a configured `session_file`, the default session files in the home directory are tried.
+fn read_session(session_file: Option<&Path>) -> Option<String> {
+    let from_env = SESSION_ENV_VARS
+        .iter()
+        .find_map(|name| env::var(name).ok())
+        .filter(|session| !session.trim().is_empty());
+
+    from_env
+        .or_else(|| {
+            if let Some(path) = session_file {
+                return fs::read_to_string(path).ok();
+            }
+
+            let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+            SESSION_FILE_NAMES
+                .iter()
+                .find_map(|name| fs::read_to_string(PathBuf::from(&home).join(name)).ok())
+        })
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::{
+        env, fs,
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+        time::Instant,
+    };
+
+    use std::time::Duration;
+
+    use super::{unix_millis, wait_time, AocClient, AocClientError, Verdict};
+    use crate::{day, template::PuzzleId};
+
+    /// Serve a single request with the given status and body, returns the base URL and the received request.
+    /// The request body is the last line of the received request.
+    fn serve_once(status: &str, body: &str) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+
+        let handle = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(&stream);
+            let mut request: Vec<String> = reader
+                .by_ref()
+                .lines()
+                .map_while(Result::ok)
+                .take_while(|line| !line.is_empty())
+                .collect();
+
+            let body_len = request
+                .iter()
+                .find_map(|line| line.strip_prefix("Content-Length: "))
+                .map_or(0, |len| len.parse().unwrap());
+            let mut body = vec![0; body_len];
+            reader.read_exact(&mut body).unwrap();
+            request.push(String::from_utf8(body).unwrap());
+
+            stream.write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn downloads_inputs_with_session() {
+        let (base_url, server) = serve_once("200 OK", "1\n2\n");
+        let client = AocClient::new(&base_url, Some("secret".into()));
+
+        assert_eq!(
+            client.get_input(PuzzleId::new(2024, day!(6))).unwrap(),
+            "1\n2\n"
+        );
+
+        let request = server.join().unwrap();
+        assert_eq!(request[0], "GET /2024/day/6/input HTTP/1.1");
+        assert!(request.iter().any(|line| line == "Cookie: session=secret"));
+    }
+
+    #[test]
+    fn converts_puzzle_pages() {
+        let (base_url, server) = serve_once(
+            "200 OK",
+            r#"<article class="day-desc"><h2>--- Day 6 ---</h2></article>"#,
+        );
+        let client = AocClient::new(&base_url, None);
+
+        assert_eq!(
+            client.get_puzzle(PuzzleId::new(2024, day!(6))).unwrap(),
+            "## --- Day 6 ---\n"
+        );
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn reports_status_errors() {
+        let (base_url, server) = serve_once("404 Not Found", "");
+        let client = AocClient::new(&base_url, Some("secret".into()));
+
+        assert!(matches!(
+            client.get_input(PuzzleId::new(2024, day!(25))),
+            Err(AocClientError::Status(404))
+        ));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (base_url, server) = serve_once(
+            "200 OK",
+            "<main><article><p>That's the right answer! You are one gold star closer.</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, Some("secret".into()));
+
+        assert_eq!(
+            client
+                .submit_answer(PuzzleId::new(2023, day!(6)), 2, "1234")
+                .unwrap(),
+            Verdict::Correct
+        );
+
+        let request = server.join().unwrap();
+        assert_eq!(request[0], "POST /2023/day/6/answer HTTP/1.1");
+        assert_eq!(request.last().unwrap(), "level=2&answer=1234");
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        let wrong = "That's not the right answer. If you're stuck, make sure you're using the full input data; \
+            there are also some general tips on the about page. Please wait one minute before trying again.";
+        assert_eq!(Verdict::parse(wrong), Some(Verdict::Wrong));
+        assert_eq!(
+            Verdict::parse("That's not the right answer; your answer is too high."),
+            Some(Verdict::TooHigh)
+        );
+        assert_eq!(
+            Verdict::parse("That's not the right answer; your answer is too low."),
+            Some(Verdict::TooLow)
+        );
+        assert_eq!(
+            Verdict::parse(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            Some(Verdict::AlreadySolved)
+        );
+        assert_eq!(
+            Verdict::parse("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait."),
+            Some(Verdict::RateLimited(Some(Duration::from_secs(83))))
+        );
+        assert_eq!(
+            Verdict::parse("You gave an answer too recently."),
+            Some(Verdict::RateLimited(None))
+        );
+        assert_eq!(Verdict::parse("<html></html>"), None);
+    }
+
+    #[test]
+    fn throttles_requests() {
+        assert_eq!(wait_time(None, 10_000), Duration::ZERO);
+        assert_eq!(wait_time(Some(9_500), 10_000), Duration::from_millis(1_500));
+        assert_eq!(wait_time(Some(5_000), 10_000), Duration::ZERO);
+
+        // submissions wait for the interval since the last request, too.
+        let throttle_file = env::temp_dir().join(format!("aoc-throttle-{}", std::process::id()));
+        fs::write(&throttle_file, (unix_millis() - 1_800).to_string()).unwrap();
+
+        let (base_url, server) = serve_once(
+            "200 OK",
+            "<main><article><p>That's the right answer!</p></article></main>",
+        );
+        let client = AocClient::new(&base_url, Some("secret".into())).with_throttle(&throttle_file);
+
+        let started = Instant::now();
+        client
+            .submit_answer(PuzzleId::new(2024, day!(6)), 1, "42")
+            .unwrap();
+        server.join().unwrap();
+
+        let last_request: u128 = fs::read_to_string(&throttle_file).unwrap().parse().unwrap();
+        fs::remove_file(&throttle_file).unwrap();
+
+        assert!(started.elapsed() >= Duration::from_millis(150));
+        assert!(last_request >= unix_millis() - started.elapsed().as_millis());
+    }
+
+    #[test]
+    fn requires_session_for_inputs() {
+        let client = AocClient::new("http://127.0.0.1:1", None);
+        assert!(matches!(
+            client.get_input(PuzzleId::new(2024, day!(1))),
+            Err(AocClientError::SessionNotFound)
+        ));
+    }
+}
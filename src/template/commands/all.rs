@@ -1,5 +1,26 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::{process, time::Duration};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    all_days,
+    run_multi::{run_multi, RunOptions},
+};
+
+pub fn handle(year: u16, is_release: bool, jobs: usize, timeout: Option<Duration>) {
+    let run = run_multi(
+        year,
+        &all_days(year).collect(),
+        &RunOptions {
+            is_release,
+            is_timed: false,
+            jobs,
+            timeout,
+            count_allocs: false,
+            spans: false,
+            all_inputs: false,
+        },
+    );
+
+    if run.has_failures {
+        process::exit(1);
+    }
 }
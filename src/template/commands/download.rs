@@ -1,14 +1,55 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use std::{fs, path::Path, process};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+use crate::template::{aoc_client::AocClient, PuzzleId};
+
+/// Download the input and puzzle of a day. An existing, non-empty input is kept unless `force` is set.
+pub fn handle(puzzle: PuzzleId, force: bool) {
+    let client = AocClient::from_env();
+    let data_dir = puzzle.data_dir();
+
+    let input_path = data_dir.join(format!("inputs/{}.txt", puzzle.day));
+    let has_input = fs::read_to_string(&input_path).is_ok_and(|input| !input.trim().is_empty());
+
+    if has_input && !force {
+        println!(
+            "🎄 Input \"{}\" already exists, pass --force to download it again.",
+            input_path.display()
+        );
+    } else {
+        let input = client
+            .get_input(puzzle)
+            .unwrap_or_else(|e| exit_with_error(&e));
+        write_file(&input_path, &input);
+        println!(
+            "🎄 Successfully wrote input to \"{}\".",
+            input_path.display()
+        );
+    }
+
+    let description = client
+        .get_puzzle(puzzle)
+        .unwrap_or_else(|e| exit_with_error(&e));
+    let puzzle_path = data_dir.join(format!("puzzles/{}.md", puzzle.day));
+    write_file(&puzzle_path, &description);
+
+    println!(
+        "🎄 Successfully wrote puzzle to \"{}\".",
+        puzzle_path.display()
+    );
+}
+
+fn write_file(path: &Path, contents: &str) {
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(path, contents));
+
+    if let Err(e) = result {
+        exit_with_error(&format!("could not write \"{}\": {e}", path.display()));
     }
+}
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+fn exit_with_error(e: &dyn std::fmt::Display) -> ! {
+    eprintln!("Failed to download puzzle: {e}");
+    process::exit(1);
 }
@@ -0,0 +1,143 @@
+use std::{fs, process};
+
+use crate::template::{
+    answers,
+    puzzle::{self, PuzzlePart},
+    Day, PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+/// Options of the `examples` command.
+#[derive(Clone, Debug, Default)]
+pub struct ExamplesOptions {
+    /// Number of the code block to store as example, lists the code blocks if `None`.
+    pub block: Option<usize>,
+    /// Expected answer, defaults to the last answer candidate of the part.
+    pub answer: Option<String>,
+    /// Part the example belongs to, defaults to the part the code block appears in.
+    pub part: Option<u8>,
+    /// Replace an existing example with different contents.
+    pub force: bool,
+}
+
+pub fn handle(puzzle: PuzzleId, options: &ExamplesOptions) {
+    let day = puzzle.day;
+    let puzzle_path = puzzle.data_dir().join(format!("puzzles/{day}.md"));
+    let Ok(markdown) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "Puzzle \"{}\" not found. Run `cargo download {day}` first.",
+            puzzle_path.display()
+        );
+        process::exit(1);
+    };
+
+    let parts = puzzle::parse_markdown(&markdown);
+
+    match options.block {
+        None => print_parts(day, &parts),
+        Some(block) => store_example(puzzle, &parts, block, options),
+    }
+}
+
+/// Code blocks of all parts with their part, numbered from 1 in the order they appear.
+fn numbered_blocks(parts: &[PuzzlePart]) -> impl Iterator<Item = (usize, u8, &String)> {
+    parts
+        .iter()
+        .flat_map(|part| part.code_blocks.iter().map(move |block| (part.part, block)))
+        .enumerate()
+        .map(|(i, (part, block))| (i + 1, part, block))
+}
+
+fn print_parts(day: Day, parts: &[PuzzlePart]) {
+    for (number, part, block) in numbered_blocks(parts) {
+        println!("{ANSI_BOLD}Block {number}{ANSI_RESET} {ANSI_ITALIC}(part {part}){ANSI_RESET}");
+        println!("{block}");
+        println!();
+    }
+
+    for part in parts {
+        if !part.answers.is_empty() {
+            println!(
+                "{ANSI_BOLD}Answer candidates for part {}:{ANSI_RESET} {}",
+                part.part,
+                part.answers.join(", ")
+            );
+        }
+    }
+
+    println!("---");
+    println!("🎄 Type `cargo examples {day} --block <n> [--answer <answer>]` to store an example.");
+}
+
+fn store_example(puzzle: PuzzleId, parts: &[PuzzlePart], block: usize, options: &ExamplesOptions) {
+    let day = puzzle.day;
+    let Some((_, block_part, contents)) = numbered_blocks(parts).find(|(n, _, _)| *n == block)
+    else {
+        eprintln!("Block {block} not found, run `cargo examples {day}` to list the code blocks.");
+        process::exit(1);
+    };
+
+    let part = options.part.unwrap_or(block_part);
+    if !(1..=2).contains(&part) {
+        eprintln!("Unexpected part {part}, puzzles have two parts.");
+        process::exit(1);
+    }
+    let contents = format!("{contents}\n");
+
+    // NOTE: part two falls back to the shared example, a separate file is only needed if they differ.
+    let examples_dir = puzzle.data_dir().join("examples");
+    let shared_path = examples_dir.join(format!("{day}.txt"));
+    let example_path = if part == 1 || fs::read_to_string(&shared_path).is_ok_and(|x| x == contents)
+    {
+        shared_path
+    } else {
+        examples_dir.join(format!("{day}-{part}.txt"))
+    };
+
+    let existing = fs::read_to_string(&example_path).unwrap_or_default();
+    if !existing.trim().is_empty() && existing != contents && !options.force {
+        eprintln!(
+            "Example \"{}\" already exists, pass --force to replace it.",
+            example_path.display()
+        );
+        process::exit(1);
+    }
+
+    if let Err(e) =
+        fs::create_dir_all(&examples_dir).and_then(|()| fs::write(&example_path, &contents))
+    {
+        eprintln!("Failed to write \"{}\": {e}", example_path.display());
+        process::exit(1);
+    }
+    println!(
+        "🎄 Successfully wrote example to \"{}\".",
+        example_path.display()
+    );
+
+    let answer = options.answer.clone().or_else(|| {
+        // NOTE: puzzles usually emphasize the answer of an example last, but this is a guess.
+        let candidate = parts
+            .iter()
+            .find(|p| p.part == part)
+            .and_then(|p| p.answers.last().cloned())?;
+        println!(
+            "Picked {ANSI_BOLD}{candidate}{ANSI_RESET}, the last answer candidate of part {part}. Pass --answer <answer> if the example expects another answer."
+        );
+        Some(candidate)
+    });
+
+    let Some(answer) = answer else {
+        println!("No answer candidate found for part {part}, pass --answer to record the expected answer.");
+        return;
+    };
+
+    match answers::store_example_answer(puzzle, part, &answer) {
+        Ok(()) => println!(
+            "🎄 Successfully recorded {answer} as expected answer for part {part} to \"{}\".",
+            answers::get_example_answer_path(puzzle, part).display()
+        ),
+        Err(e) => {
+            eprintln!("Failed to record expected answer: {e}");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::{
+    fs, process,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{aoc_client::AocClient, data_dir, leaderboard::Leaderboard, Day};
+
+/// The site asks to not fetch a leaderboard more often than every 15 minutes.
+const CACHE_DURATION: Duration = Duration::from_secs(15 * 60);
+
+/// Print a private leaderboard, read from `file` or fetched by its `id`.
+/// Fetched leaderboards are cached in `data/{year}/leaderboards/{id}.json`.
+pub fn handle(year: u16, id: Option<String>, file: Option<String>, day: Option<Day>) {
+    let leaderboard = match (file, id) {
+        (Some(file), _) => parse(&fs::read_to_string(&file).unwrap_or_else(|e| {
+            eprintln!("Failed to read \"{file}\": {e}");
+            process::exit(1);
+        })),
+        (None, Some(id)) => fetch_cached(year, &id),
+        (None, None) => {
+            eprintln!("Unexpected command-line input. Format: cargo leaderboard <id> [--file <path>] [--day <day>]");
+            process::exit(1);
+        }
+    };
+
+    println!("{}", leaderboard.render(day.or(leaderboard.latest_day())));
+}
+
+fn parse(json: &str) -> Leaderboard {
+    Leaderboard::try_from(json).unwrap_or_else(|e| {
+        eprintln!("Failed to parse leaderboard: {e}");
+        process::exit(1);
+    })
+}
+
+/// Fetch a leaderboard, or read it from the cache if it was fetched recently.
+/// Only leaderboards that parse are cached, so an error page is not served from the cache.
+fn fetch_cached(year: u16, id: &str) -> Leaderboard {
+    let cache_dir = data_dir(year).join("leaderboards");
+    let cache_path = cache_dir.join(format!("{id}.json"));
+
+    let age = fs::metadata(&cache_path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| SystemTime::now().duration_since(modified).ok());
+
+    if let Some(age) = age.filter(|age| *age < CACHE_DURATION) {
+        let cached = fs::read_to_string(&cache_path)
+            .ok()
+            .and_then(|json| Leaderboard::try_from(json.as_str()).ok());
+
+        if let Some(leaderboard) = cached {
+            println!(
+                "Using the leaderboard fetched {} minutes ago.",
+                age.as_secs() / 60
+            );
+            return leaderboard;
+        }
+    }
+
+    let json = AocClient::from_env()
+        .get_leaderboard(year, id)
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to fetch leaderboard: {e}");
+            process::exit(1);
+        });
+    let leaderboard = parse(&json);
+
+    if let Err(e) = fs::create_dir_all(&cache_dir).and_then(|()| fs::write(&cache_path, &json)) {
+        eprintln!("Failed to cache leaderboard: {e}");
+    }
+
+    leaderboard
+}
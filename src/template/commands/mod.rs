@@ -1,6 +1,11 @@
 pub mod all;
 pub mod download;
+pub mod examples;
+pub mod leaderboard;
 pub mod read;
+pub mod report;
 pub mod scaffold;
 pub mod solve;
+pub mod status;
 pub mod time;
+pub mod verify;
@@ -1,15 +1,67 @@
-use std::process;
+use std::{fs, path::PathBuf, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{
+    aoc_client::{AocClient, AocClientError},
+    puzzle, PuzzleId,
+};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+/// Print the puzzle description of a day, fetching it if it was not downloaded yet.
+/// With `refresh`, the description is fetched again and only the part two section is printed.
+pub fn handle(puzzle: PuzzleId, refresh: bool) {
+    if refresh {
+        if let Err(e) = self::refresh(puzzle) {
+            eprintln!("Failed to refresh puzzle: {e}");
+            process::exit(1);
+        }
+        return;
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let description = match fs::read_to_string(get_puzzle_path(puzzle)) {
+        Ok(description) if !description.trim().is_empty() => description,
+        _ => fetch(puzzle).unwrap_or_else(|e| {
+            eprintln!("Failed to fetch puzzle: {e}");
+            process::exit(1);
+        }),
     };
+
+    println!("{description}");
+}
+
+/// Fetch the puzzle description of a day again and print its part two section, if unlocked.
+pub fn refresh(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let description = fetch(puzzle)?;
+
+    match puzzle::part_two_section(&description) {
+        Some(part_two) => println!("{part_two}"),
+        None => println!(
+            "🎄 Part two of day {} is not unlocked yet, solve part one first.",
+            puzzle.day
+        ),
+    }
+
+    Ok(())
+}
+
+fn get_puzzle_path(puzzle: PuzzleId) -> PathBuf {
+    puzzle
+        .data_dir()
+        .join("puzzles")
+        .join(format!("{}.md", puzzle.day))
+}
+
+/// Fetch the puzzle description of a day and store it.
+fn fetch(puzzle: PuzzleId) -> Result<String, AocClientError> {
+    let description = AocClient::from_env().get_puzzle(puzzle)?;
+
+    let puzzle_path = get_puzzle_path(puzzle);
+    let result = puzzle_path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::write(&puzzle_path, &description));
+
+    if let Err(e) = result {
+        eprintln!("Failed to write \"{}\": {e}", puzzle_path.display());
+    }
+
+    Ok(description)
 }
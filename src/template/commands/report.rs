@@ -0,0 +1,25 @@
+use std::{fs, process};
+
+use crate::template::report::{self, ReportFormat};
+
+pub fn handle(year: u16, format: ReportFormat, output: Option<String>) {
+    let days = report::collect_stored(year);
+
+    if days.is_empty() {
+        eprintln!("Nothing to report. Run `cargo time --store` to store timings first.");
+        process::exit(1);
+    }
+
+    let report = report::render(&days, format);
+
+    match output {
+        Some(path) => match fs::write(&path, report) {
+            Ok(()) => println!("🎄 Successfully wrote report to \"{path}\"."),
+            Err(e) => {
+                eprintln!("Failed to write report: {e}");
+                process::exit(1);
+            }
+        },
+        None => print!("{report}"),
+    }
+}
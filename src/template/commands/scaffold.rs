@@ -1,15 +1,17 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{config, PuzzleId};
 
+/// Built-in template of a solution, used unless a template is configured.
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+fn safe_create_file(path: &Path, overwrite: bool) -> Result<File, std::io::Error> {
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -19,7 +21,10 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
     file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
+fn create_file(path: &Path) -> Result<File, std::io::Error> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,10 +32,24 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Read the configured template, falls back to the built-in one.
+fn read_template() -> String {
+    let Some(path) = &config::get().template else {
+        return MODULE_TEMPLATE.into();
+    };
+
+    fs::read_to_string(path).unwrap_or_else(|e| {
+        eprintln!("Failed to read template \"{}\": {e}", path.display());
+        process::exit(1);
+    })
+}
+
+pub fn handle(puzzle: PuzzleId, overwrite: bool) {
+    let day = puzzle.day;
+    let input_path = puzzle.data_dir().join(format!("inputs/{day}.txt"));
+    let example_path = puzzle.data_dir().join(format!("examples/{day}.txt"));
+    let module_path = puzzle.bin_path();
+    let template = read_template();
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -41,12 +60,12 @@ pub fn handle(day: Day, overwrite: bool) {
     };
 
     match file.write_all(
-        MODULE_TEMPLATE
+        template
             .replace("%DAY_NUMBER%", &day.into_inner().to_string())
             .as_bytes(),
     ) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!("Created module file \"{}\"", module_path.display());
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -56,7 +75,7 @@ pub fn handle(day: Day, overwrite: bool) {
 
     match create_file(&input_path) {
         Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+            println!("Created empty input file \"{}\"", input_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
@@ -66,7 +85,7 @@ pub fn handle(day: Day, overwrite: bool) {
 
     match create_file(&example_path) {
         Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+            println!("Created empty example file \"{}\"", example_path.display());
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
@@ -75,5 +94,11 @@ pub fn handle(day: Day, overwrite: bool) {
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    // the year only needs to be passed if it's not the configured one.
+    let year_arg = if config::get().year == Some(puzzle.year) {
+        String::new()
+    } else {
+        format!(" --year {}", puzzle.year)
+    };
+    println!("🎄 Type `cargo solve {day}{year_arg}` to run your solution.");
 }
@@ -1,9 +1,111 @@
-use std::process::{Command, Stdio};
+use std::path::PathBuf;
+use std::process::{self, Command, Stdio};
+use std::time::Duration;
 
-use crate::template::Day;
+use crate::template::run_multi::child_commands;
+use crate::template::timings::Timings;
+use crate::template::{inputs, watch, PuzzleId};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Options of the `solve` command, mirrored to the solution bin.
+#[derive(Clone, Debug, Default)]
+pub struct SolveOptions {
+    pub release: bool,
+    /// Profile heap allocations with DHAT.
+    pub dhat: bool,
+    pub submit_part: Option<u8>,
+    pub timeout: Option<Duration>,
+    pub count_allocs: bool,
+    pub spans: bool,
+    /// Run the solution against every input of the day.
+    pub all_inputs: bool,
+    /// Path of a custom input, `-` reads the input from stdin.
+    pub input: Option<String>,
+    /// Run the solution against its examples.
+    pub example: bool,
+    /// Only run a single part.
+    pub part: Option<u8>,
+    /// Re-run the solution whenever its source, input or examples change.
+    pub watch: bool,
+    /// Also run the tests of the day in watch mode.
+    pub test: bool,
+}
+
+pub fn handle(puzzle: PuzzleId, options: &SolveOptions) {
+    if !options.watch {
+        let code = run(puzzle, options);
+        if code != 0 {
+            process::exit(code);
+        }
+        return;
+    }
+
+    if options.submit_part.is_some() || options.input.as_deref() == Some("-") {
+        eprintln!("--watch can not be combined with --submit or reading the input from stdin.");
+        process::exit(1);
+    }
+
+    watch::watch(
+        || watched_paths(puzzle, options),
+        || {
+            let code = run(puzzle, options);
+            if options.test && code == 0 {
+                run_tests(puzzle);
+            }
+        },
+    );
+}
+
+/// Files that trigger a re-run in watch mode. Files that do not exist yet are watched for creation.
+fn watched_paths(puzzle: PuzzleId, options: &SolveOptions) -> Vec<PathBuf> {
+    let day = puzzle.day;
+    let data_dir = puzzle.data_dir();
+    let mut paths = vec![
+        puzzle.bin_path(),
+        PathBuf::from("src/lib.rs"),
+        data_dir.join(format!("inputs/{day}.txt")),
+        data_dir.join(format!("examples/{day}.txt")),
+        data_dir.join(format!("examples/{day}-1.txt")),
+        data_dir.join(format!("examples/{day}-2.txt")),
+    ];
+
+    paths.extend(inputs::list_named_inputs(puzzle).into_iter().filter_map(
+        |input| match input.source {
+            inputs::InputSource::File(path) => Some(path),
+            inputs::InputSource::Stdin => None,
+        },
+    ));
+
+    if let Some(input) = &options.input {
+        paths.push(PathBuf::from(input));
+    }
+
+    paths
+}
+
+fn run_tests(puzzle: PuzzleId) {
+    println!();
+    let _ = Command::new("cargo")
+        .args(["test", "--bin", &puzzle.bin_name()])
+        .status();
+}
+
+/// Run the solution bin once, returns its exit code.
+fn run(puzzle: PuzzleId, options: &SolveOptions) -> i32 {
+    let SolveOptions {
+        release,
+        dhat,
+        submit_part,
+        timeout,
+        count_allocs,
+        spans,
+        all_inputs,
+        input,
+        example,
+        part,
+        ..
+    } = options.clone();
+
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -12,8 +114,15 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
             "--features".to_string(),
             "dhat-heap".to_string(),
         ]);
-    } else if release {
-        cmd_args.push("--release".to_string());
+    } else {
+        if release {
+            cmd_args.push("--release".to_string());
+        }
+
+        if count_allocs {
+            cmd_args.push("--features".to_string());
+            cmd_args.push("count-allocs".to_string());
+        }
     }
 
     cmd_args.push("--".to_string());
@@ -23,6 +132,41 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if let Some(timeout) = timeout {
+        cmd_args.push("--timeout".to_string());
+        cmd_args.push(timeout.as_secs_f64().to_string());
+    }
+
+    if spans {
+        cmd_args.push("--spans".to_string());
+    }
+
+    if all_inputs {
+        cmd_args.push("--all-inputs".to_string());
+    }
+
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input);
+    }
+
+    if example {
+        cmd_args.push("--example".to_string());
+    }
+
+    if let Some(part) = part {
+        cmd_args.push("--part".to_string());
+        cmd_args.push(part.to_string());
+    }
+
+    if dhat {
+        // capture output of profiled runs to store the measured memory stats.
+        let output = child_commands::run_streamed(&cmd_args).unwrap();
+        store_memory(puzzle, &output.stdout);
+
+        return i32::from(output.failed);
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -30,5 +174,40 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+
+    if status.success() {
+        0
+    } else {
+        status.code().unwrap_or(1)
+    }
+}
+
+/// Store memory stats printed by a profiled solution in the timings file.
+fn store_memory(puzzle: PuzzleId, output: &[String]) {
+    let PuzzleId { year, day } = puzzle;
+    let measured = child_commands::parse_exec_time(output, day);
+
+    if measured.part_1_memory.is_none() && measured.part_2_memory.is_none() {
+        return;
+    }
+
+    let mut timings = Timings::read_from_file(year);
+
+    // NOTE: profiled runs are much slower, so their durations are not stored. Without a stored timing, a new row
+    // would show the day as benched with empty timings, so memory stats are only added to existing rows.
+    let Some(timing) = timings.data.iter_mut().find(|t| t.day == day) else {
+        println!(
+            "Not storing memory stats, day {day} has no stored timings. Run `cargo time {day} --store` first."
+        );
+        return;
+    };
+
+    timing.part_1_memory = measured.part_1_memory.or(timing.part_1_memory);
+    timing.part_2_memory = measured.part_2_memory.or(timing.part_2_memory);
+
+    match timings.store_file(year) {
+        Ok(()) => println!("Stored memory stats for day {day}."),
+        Err(e) => eprintln!("Failed to store memory stats: {e}"),
+    }
 }
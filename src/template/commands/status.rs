@@ -0,0 +1,30 @@
+use std::process::{Command, Stdio};
+
+use crate::template::{status, timings::Timings, PuzzleId, ANSI_BOLD, ANSI_RESET};
+
+/// Print the advent calendar overview of an event. With `test`, the tests of every solution are run.
+pub fn handle(year: u16, test: bool) {
+    let run_tests: &dyn Fn(PuzzleId) -> bool = &|puzzle| {
+        println!("Running tests of day {}...", puzzle.day);
+        Command::new("cargo")
+            .args(["test", "--quiet", "--bin", &puzzle.bin_name()])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    };
+
+    let days = status::collect(
+        year,
+        &Timings::read_from_file(year),
+        test.then_some(run_tests),
+    );
+
+    if test {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Advent of Code {year}{ANSI_RESET}");
+    println!();
+    println!("{}", status::render(&days));
+}
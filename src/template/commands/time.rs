@@ -1,40 +1,182 @@
 use std::collections::HashSet;
+use std::process;
+use std::time::Duration;
 
-use crate::template::run_multi::run_multi;
+use crate::template::compare::{compare, print_comparison};
+use crate::template::history::{self, HistoryEntry};
+use crate::template::run_multi::{run_multi, RunOptions};
 use crate::template::timings::Timings;
 use crate::template::{all_days, readme_benchmarks, Day};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
-
-    let days_to_run = day.map_or_else(
-        || {
-            if run_all {
-                all_days().collect()
-            } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
-                    .collect()
-            }
+/// Options of the `time` command.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TimeOptions {
+    /// Bench all days, including days that are fully benched already.
+    pub all: bool,
+    /// Store the timings and update the readme.
+    pub store: bool,
+    pub timeout: Option<Duration>,
+    /// Threshold in percent a part may get slower by, compares against stored timings if set.
+    pub compare_threshold: Option<f64>,
+    pub count_allocs: bool,
+    pub spans: bool,
+}
+
+impl TimeOptions {
+    /// Build profile recorded in the history, instrumented allocators are part of it as they slow down solutions.
+    fn profile(&self) -> &'static str {
+        if self.count_allocs {
+            "release+count-allocs"
+        } else {
+            "release"
+        }
+    }
+}
+
+/// Days to bench when no day is passed. `--all` selects every day, and so does `--compare`, as the stored
+/// days are the ones it compares against. Otherwise days that are fully benched already are skipped.
+fn select_days(
+    year: u16,
+    day: Option<Day>,
+    stored_timings: &Timings,
+    options: &TimeOptions,
+) -> HashSet<Day> {
+    if let Some(day) = day {
+        return HashSet::from([day]);
+    }
+
+    if options.all || options.compare_threshold.is_some() {
+        all_days(year).collect()
+    } else {
+        all_days(year)
+            .filter(|day| !stored_timings.is_day_complete(*day))
+            .collect()
+    }
+}
+
+/// Bench the selected days of an event. When `compare_threshold` is set, the new timings are compared against the
+/// stored timings and the command fails if a part got slower by more than the threshold (in percent).
+pub fn handle(year: u16, day: Option<Day>, options: &TimeOptions) {
+    let TimeOptions {
+        all: _,
+        store,
+        timeout,
+        compare_threshold,
+        count_allocs,
+        spans,
+    } = *options;
+
+    let stored_timings = Timings::read_from_file(year);
+
+    let days_to_run = select_days(year, day, &stored_timings, options);
+
+    let run = run_multi(
+        year,
+        &days_to_run,
+        &RunOptions {
+            is_release: true,
+            is_timed: true,
+            jobs: 1,
+            timeout,
+            count_allocs,
+            spans,
+            all_inputs: false,
         },
-        |day| HashSet::from([day]),
     );
+    let timings = run.timings.unwrap();
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let has_regressions = compare_threshold
+        .is_some_and(|threshold| print_comparison(&compare(&stored_timings, &timings), threshold));
 
     if store {
+        // NOTE: create the entry first, a committed timings file would mark the commit as dirty once it's written.
+        let entry = HistoryEntry::new(&timings, options.profile());
+
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
+
+        if let Err(e) = history::append(year, &entry) {
+            eprintln!("Failed to append benchmarks to history: {e}");
+        }
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+            Err(e) => {
+                eprintln!("Failed to store updated benchmarks: {e}");
             }
         }
     }
+
+    if run.has_failures || has_regressions {
+        process::exit(1);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{select_days, TimeOptions};
+    use crate::{
+        day,
+        template::{
+            compare::compare,
+            timings::{Timing, Timings},
+        },
+    };
+
+    fn timings(part_1: &str, part_2: &str) -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: day!(1),
+                part_1: Some(part_1.into()),
+                part_2: Some(part_2.into()),
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[test]
+    fn records_instrumented_allocators_in_the_profile() {
+        assert_eq!(TimeOptions::default().profile(), "release");
+
+        let options = TimeOptions {
+            count_allocs: true,
+            ..Default::default()
+        };
+        assert_eq!(options.profile(), "release+count-allocs");
+    }
+
+    #[test]
+    fn skips_fully_benched_days() {
+        let stored = timings("10ms", "20ms");
+        let days = select_days(2024, None, &stored, &TimeOptions::default());
+
+        assert!(!days.contains(&day!(1)));
+        assert!(days.contains(&day!(2)));
+    }
+
+    #[test]
+    fn compare_rebenches_stored_days_and_flags_regressions() {
+        let stored = timings("10ms", "20ms");
+        let options = TimeOptions {
+            compare_threshold: Some(10.0),
+            ..Default::default()
+        };
+
+        let days = select_days(2024, None, &stored, &options);
+        assert!(days.contains(&day!(1)));
+
+        // the re-benched day got slower in part 2.
+        let current = timings("10ms", "30ms");
+        let regressions: Vec<u8> = compare(&stored, &current)
+            .iter()
+            .filter(|delta| delta.is_regression(10.0))
+            .map(|delta| delta.part)
+            .collect();
+        assert_eq!(regressions, vec![2]);
+    }
 }
@@ -0,0 +1,56 @@
+use std::{collections::HashSet, process, time::Duration};
+
+use crate::template::{
+    all_days,
+    answers::{self, get_answer_path},
+    run_multi::{run_multi, RunOptions},
+    Day, PuzzleId, ANSI_BOLD, ANSI_RESET,
+};
+
+pub fn handle(year: u16, day: Option<Day>, jobs: usize, timeout: Option<Duration>) {
+    // when no day is passed, verify every day that has at least one known answer.
+    let days_to_run: HashSet<Day> = day.map_or_else(
+        || {
+            all_days(year)
+                .filter(|day| answers::has_answers(PuzzleId::new(year, *day)))
+                .collect()
+        },
+        |day| HashSet::from([day]),
+    );
+
+    if let Some(day) = day {
+        let puzzle = PuzzleId::new(year, day);
+        if !answers::has_answers(puzzle) {
+            eprintln!(
+                "No known answers for {puzzle}. Add them to e.g. \"{}\".",
+                get_answer_path(puzzle, None, 1).display()
+            );
+            process::exit(1);
+        }
+    } else if days_to_run.is_empty() {
+        println!("No known answers found, nothing to verify.");
+        return;
+    }
+
+    let run = run_multi(
+        year,
+        &days_to_run,
+        &RunOptions {
+            is_release: true,
+            is_timed: false,
+            jobs,
+            timeout,
+            count_allocs: false,
+            spans: false,
+            all_inputs: true,
+        },
+    );
+
+    println!();
+    if run.has_failures {
+        eprintln!("{ANSI_BOLD}✘ Verification failed.{ANSI_RESET} Some parts did not match their known answer or did not finish.");
+        process::exit(1);
+    }
+
+    println!("{ANSI_BOLD}✔ All known answers match.{ANSI_RESET}");
+}
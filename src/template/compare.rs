@@ -0,0 +1,181 @@
+/// Module that compares fresh benchmark timings against stored timings.
+use std::time::Duration;
+
+use crate::template::timings::Timings;
+use crate::template::{Day, ANSI_BOLD, ANSI_GREEN, ANSI_ITALIC, ANSI_RED, ANSI_RESET};
+
+/// Regression threshold used when `--threshold` is not passed, in percent.
+pub const DEFAULT_THRESHOLD_PERCENT: f64 = 10.0;
+
+/// Change of a single part's runtime between two benchmark runs.
+#[derive(Clone, Debug)]
+pub struct PartDelta {
+    pub day: Day,
+    pub part: u8,
+    pub stored_nanos: f64,
+    pub current_nanos: f64,
+}
+
+impl PartDelta {
+    pub fn delta_nanos(&self) -> f64 {
+        self.current_nanos - self.stored_nanos
+    }
+
+    pub fn delta_percent(&self) -> f64 {
+        if self.stored_nanos == 0.0 {
+            return 0.0;
+        }
+        self.delta_nanos() / self.stored_nanos * 100.0
+    }
+
+    /// Returns `true` if the part got slower by more than `threshold_percent`.
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.delta_percent() > threshold_percent
+    }
+}
+
+/// Pair up parts that are present in both sets of timings.
+pub fn compare(stored: &Timings, current: &Timings) -> Vec<PartDelta> {
+    let mut deltas = vec![];
+
+    for timing in &current.data {
+        let Some(stored_timing) = stored.data.iter().find(|t| t.day == timing.day) else {
+            continue;
+        };
+
+        for part in [1, 2] {
+            if let (Some(stored_nanos), Some(current_nanos)) =
+                (stored_timing.part_nanos(part), timing.part_nanos(part))
+            {
+                deltas.push(PartDelta {
+                    day: timing.day,
+                    part,
+                    stored_nanos,
+                    current_nanos,
+                });
+            }
+        }
+    }
+
+    deltas
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos.abs() as u64))
+}
+
+fn format_delta(delta: &PartDelta) -> String {
+    let sign = if delta.delta_nanos() < 0.0 { "-" } else { "+" };
+    format!(
+        "{sign}{} / {:+.1}%",
+        format_nanos(delta.delta_nanos()),
+        delta.delta_percent()
+    )
+}
+
+/// Print the comparison with stored timings. Returns `true` if any part regressed beyond the threshold.
+pub fn print_comparison(deltas: &[PartDelta], threshold_percent: f64) -> bool {
+    println!();
+    println!(
+        "{ANSI_BOLD}Comparison with stored timings{ANSI_RESET} (threshold: {threshold_percent}%)"
+    );
+    println!("------");
+
+    if deltas.is_empty() {
+        println!("No stored timings to compare against.");
+        return false;
+    }
+
+    let mut regressions = 0;
+
+    for delta in deltas {
+        let is_regression = delta.is_regression(threshold_percent);
+        let color = if is_regression {
+            ANSI_RED
+        } else if delta.delta_nanos() < 0.0 {
+            ANSI_GREEN
+        } else {
+            ""
+        };
+
+        let flag = if is_regression {
+            regressions += 1;
+            format!(" {ANSI_BOLD}{ANSI_RED}▲ regression{ANSI_RESET}")
+        } else {
+            String::new()
+        };
+
+        println!(
+            "Day {} Part {}: {} → {} {color}({}){ANSI_RESET}{flag}",
+            delta.day,
+            delta.part,
+            format_nanos(delta.stored_nanos),
+            format_nanos(delta.current_nanos),
+            format_delta(delta),
+        );
+    }
+
+    println!();
+    if regressions > 0 {
+        println!("{ANSI_BOLD}{regressions} part(s) regressed by more than {threshold_percent}%.{ANSI_RESET}");
+    } else {
+        println!("{ANSI_ITALIC}No regressions.{ANSI_RESET}");
+    }
+
+    regressions > 0
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::compare;
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    fn timing(day: crate::template::Day, part_1: Option<&str>, part_2: Option<&str>) -> Timing {
+        Timing {
+            day,
+            part_1: part_1.map(Into::into),
+            part_2: part_2.map(Into::into),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn compares_matching_parts() {
+        let stored = Timings {
+            data: vec![timing(day!(1), Some("10ms"), Some("20ms"))],
+        };
+        let current = Timings {
+            data: vec![timing(day!(1), Some("12ms"), Some("10ms"))],
+        };
+
+        let deltas = compare(&stored, &current);
+        assert_eq!(deltas.len(), 2);
+        assert!((deltas[0].delta_percent() - 20.0).abs() < 1e-6);
+        assert!(deltas[0].is_regression(10.0));
+        assert!(!deltas[0].is_regression(25.0));
+        assert!((deltas[1].delta_percent() + 50.0).abs() < 1e-6);
+        assert!(!deltas[1].is_regression(10.0));
+    }
+
+    #[test]
+    fn skips_parts_without_stored_timings() {
+        let stored = Timings {
+            data: vec![timing(day!(1), Some("10ms"), None)],
+        };
+        let current = Timings {
+            data: vec![
+                timing(day!(1), Some("10ms"), Some("20ms")),
+                timing(day!(2), Some("1ms"), Some("2ms")),
+            ],
+        };
+
+        let deltas = compare(&stored, &current);
+        assert_eq!(deltas.len(), 1);
+        assert_eq!(deltas[0].day, day!(1));
+        assert_eq!(deltas[0].part, 1);
+    }
+}
@@ -0,0 +1,265 @@
+/// Module that reads the project configuration from `aoc.toml` in the project root.
+///
+/// Every setting has a default, so the file and each of its keys are optional.
+/// Environment variables take precedence over the file: `AOC_YEAR` sets the year and
+/// `AOC_USER_AGENT` the User-Agent. Command line flags like `--year` take precedence over both.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+    sync::OnceLock,
+};
+
+use serde::Deserialize;
+
+use crate::template::DEFAULT_THRESHOLD_PERCENT;
+
+pub const CONFIG_FILE_NAME: &str = "aoc.toml";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Settings of the template, see the readme for a description of each setting.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    /// Year commands work on when no `--year` is passed.
+    pub year: Option<u16>,
+    /// Offset of the Advent of Code server's timezone to UTC in hours, used to determine the puzzle of today.
+    pub server_utc_offset: i32,
+    /// Directory the data of every year is stored in, as `<data_dir>/<year>/...`.
+    pub data_dir: PathBuf,
+    /// Readme the benchmark tables are written to.
+    pub readme: PathBuf,
+    /// Template new solutions are scaffolded from. Uses the built-in template when not set.
+    pub template: Option<PathBuf>,
+    /// File the session token is read from when it's not set in the environment.
+    pub session_file: Option<PathBuf>,
+    /// User-Agent sent with requests to the Advent of Code website.
+    pub user_agent: Option<String>,
+    /// Text of the markers enclosing the benchmark tables, e.g. `benchmarking table`.
+    pub benchmark_marker: String,
+    /// Default options of the benchmark tables, in the syntax of the table markers.
+    pub benchmark_table: String,
+    /// Columns of benchmark tables that don't set `columns` in their options.
+    pub benchmark_columns: Option<Vec<String>>,
+    /// Threshold in percent above which `cargo time --compare` reports a regression.
+    pub compare_threshold: f64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            year: None,
+            server_utc_offset: -5,
+            data_dir: "data".into(),
+            readme: "README.md".into(),
+            template: None,
+            session_file: None,
+            user_agent: None,
+            benchmark_marker: "benchmarking table".into(),
+            benchmark_table: String::new(),
+            benchmark_columns: None,
+            compare_threshold: DEFAULT_THRESHOLD_PERCENT,
+        }
+    }
+}
+
+/// The configuration of the project, read on first use.
+/// Exits the process if the configuration file is invalid.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| {
+        Config::load().unwrap_or_else(|e| {
+            eprintln!("Failed to read \"{CONFIG_FILE_NAME}\": {e}");
+            process::exit(1);
+        })
+    })
+}
+
+impl Config {
+    /// Read the configuration file if it exists and apply overrides from the environment.
+    pub fn load() -> Result<Self, ConfigError> {
+        let mut config = match fs::read_to_string(CONFIG_FILE_NAME) {
+            Ok(contents) => contents.parse()?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Config::default(),
+            Err(e) => return Err(ConfigError::Io(e)),
+        };
+
+        if let Ok(year) = env::var("AOC_YEAR") {
+            config.year = Some(year.trim().parse().map_err(|_| ConfigError::Invalid {
+                key: "AOC_YEAR".into(),
+                expected: "a year",
+            })?);
+        }
+
+        if let Ok(user_agent) = env::var("AOC_USER_AGENT") {
+            if !user_agent.trim().is_empty() {
+                config.user_agent = Some(user_agent);
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+impl std::str::FromStr for Config {
+    type Err = ConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let file: ConfigFile = toml::from_str(s).map_err(ConfigError::Parse)?;
+        let defaults = Config::default();
+
+        Ok(Config {
+            year: file.year,
+            server_utc_offset: file.server_utc_offset.unwrap_or(defaults.server_utc_offset),
+            data_dir: file.paths.data.unwrap_or(defaults.data_dir),
+            readme: file.paths.readme.unwrap_or(defaults.readme),
+            template: file.paths.template,
+            session_file: file.client.session_file.as_deref().map(expand_home),
+            user_agent: file.client.user_agent,
+            benchmark_marker: file.benchmarks.marker.unwrap_or(defaults.benchmark_marker),
+            benchmark_table: file.benchmarks.table.unwrap_or(defaults.benchmark_table),
+            benchmark_columns: file.benchmarks.columns,
+            compare_threshold: file
+                .benchmarks
+                .threshold
+                .unwrap_or(defaults.compare_threshold),
+        })
+    }
+}
+
+/// Layout of `aoc.toml`, every table and key is optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ConfigFile {
+    year: Option<u16>,
+    server_utc_offset: Option<i32>,
+    paths: PathsTable,
+    client: ClientTable,
+    benchmarks: BenchmarksTable,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct PathsTable {
+    data: Option<PathBuf>,
+    readme: Option<PathBuf>,
+    template: Option<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct ClientTable {
+    user_agent: Option<String>,
+    session_file: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct BenchmarksTable {
+    marker: Option<String>,
+    table: Option<String>,
+    columns: Option<Vec<String>>,
+    threshold: Option<f64>,
+}
+
+/// Expand a leading `~` to the home directory of the user.
+fn expand_home(path: &str) -> PathBuf {
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"));
+
+    match (path.strip_prefix("~/"), home) {
+        (Some(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(io::Error),
+    /// The file is not valid TOML or doesn't match the known settings.
+    Parse(toml::de::Error),
+    /// A setting has a value of the wrong type.
+    Invalid {
+        key: String,
+        expected: &'static str,
+    },
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io(e) => write!(f, "{e}"),
+            ConfigError::Parse(e) => write!(f, "{}", e.to_string().trim_end()),
+            ConfigError::Invalid { key, expected } => {
+                write!(f, "expected `{key}` to be {expected}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::Config;
+
+    #[test]
+    fn reads_settings() {
+        let config: Config = r#"
+            year = 2023
+            server_utc_offset = 1
+
+            [paths]
+            data = "aoc-data"
+            readme = "docs/README.md"
+            template = "template.txt"
+
+            [benchmarks]
+            marker = "timings"
+            table = "columns=total sort=cost"
+            columns = ["part_1", "parse"]
+            threshold = 5
+        "#
+        .parse()
+        .unwrap();
+
+        assert_eq!(config.year, Some(2023));
+        assert_eq!(config.server_utc_offset, 1);
+        assert_eq!(config.data_dir, PathBuf::from("aoc-data"));
+        assert_eq!(config.readme, PathBuf::from("docs/README.md"));
+        assert_eq!(config.template, Some(PathBuf::from("template.txt")));
+        assert_eq!(config.benchmark_marker, "timings");
+        assert_eq!(config.benchmark_table, "columns=total sort=cost");
+        assert_eq!(
+            config.benchmark_columns,
+            Some(vec!["part_1".into(), "parse".into()])
+        );
+        assert_eq!(config.compare_threshold, 5.0);
+    }
+
+    #[test]
+    fn defaults_missing_settings() {
+        let config: Config = "".parse().unwrap();
+        assert_eq!(config, Config::default());
+    }
+
+    #[test]
+    fn rejects_unknown_and_invalid_settings() {
+        for (s, message) in [
+            ("[paths]\ndate = \"data\"", "unknown field `date`"),
+            ("year = \"2024\"", "invalid type"),
+            ("year = 100000", "invalid value"),
+        ] {
+            let err = s.parse::<Config>().unwrap_err();
+            assert!(err.to_string().contains(message), "{err}");
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -1,15 +1,18 @@
 use std::error::Error;
 use std::fmt::Display;
+use std::path::PathBuf;
 use std::str::FromStr;
 
-#[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use crate::template::config;
 
 #[cfg(feature = "today")]
-const SERVER_UTC_OFFSET: i32 = -5;
+use chrono::{Datelike, FixedOffset, Utc};
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
+/// Not every event has 25 puzzles, see [`days_in_year`]. Use [`Day::for_year`] to validate a day
+/// against the event it belongs to.
+///
 /// # Display
 /// This value displays as a two digit number.
 ///
@@ -25,12 +28,38 @@ impl Day {
     /// Creates a [`Day`] from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        if day == 0 || day > MAX_DAYS {
+            return None;
+        }
+        Some(Self(day))
+    }
+
+    /// Creates a [`Day`] from the provided value if it's a day of the event of `year`,
+    /// returns [`None`] otherwise.
+    ///
+    /// ```
+    /// # use advent_of_code::template::Day;
+    /// assert!(Day::for_year(2024, 25).is_some());
+    /// assert!(Day::for_year(2025, 13).is_none());
+    /// ```
+    pub fn for_year(year: u16, day: u8) -> Option<Self> {
+        if day == 0 || day > days_in_year(year) {
             return None;
         }
         Some(Self(day))
     }
 
+    /// Parses a [`Day`] from a string, if it's a day of the event of `year`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not a number, or not a day of the event.
+    pub fn parse_for_year(year: u16, s: &str) -> Result<Self, DayFromStrError> {
+        let last_day = days_in_year(year);
+        let day = s.parse().map_err(|_| DayFromStrError { last_day })?;
+        Self::for_year(year, day).ok_or(DayFromStrError { last_day })
+    }
+
     // Not part of the public API
     #[doc(hidden)]
     pub const fn __new_unchecked(day: u8) -> Self {
@@ -38,25 +67,11 @@ impl Day {
     }
 
     /// Converts the [`Day`] into an [`u8`].
-    pub fn into_inner(self) -> u8 {
+    pub const fn into_inner(self) -> u8 {
         self.0
     }
 }
 
-#[cfg(feature = "today")]
-impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
-    pub fn today() -> Option<Self> {
-        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
-        let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
-        } else {
-            None
-        }
-    }
-}
-
 impl Display for Day {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:02}", self.0)
@@ -77,43 +92,75 @@ impl PartialOrd<u8> for Day {
 
 /* -------------------------------------------------------------------------- */
 
+/// Parses any day of advent. Use [`Day::parse_for_year`] when the year of the event is known.
 impl FromStr for Day {
     type Err = DayFromStrError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let day = s.parse().map_err(|_| DayFromStrError)?;
-        Self::new(day).ok_or(DayFromStrError)
+        let last_day = MAX_DAYS;
+        let day = s.parse().map_err(|_| DayFromStrError { last_day })?;
+        Self::new(day).ok_or(DayFromStrError { last_day })
     }
 }
 
 /// An error which can be returned when parsing a [`Day`].
 #[derive(Debug)]
-pub struct DayFromStrError;
+pub struct DayFromStrError {
+    last_day: u8,
+}
 
 impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(f, "expecting a day number between 1 and {}", self.last_day)
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
-pub fn all_days() -> AllDays {
-    AllDays::new()
+/// The highest day number of any event.
+pub const MAX_DAYS: u8 = 25;
+
+/// First event with fewer puzzles than [`MAX_DAYS`].
+const SHORT_EVENTS_SINCE: u16 = 2025;
+
+/// Number of puzzles of the events since [`SHORT_EVENTS_SINCE`].
+const SHORT_EVENT_DAYS: u8 = 12;
+
+/// Number of puzzles of the event of `year`. Events up to 2024 have 25 puzzles,
+/// later events have 12.
+///
+/// ```
+/// # use advent_of_code::template::days_in_year;
+/// assert_eq!(days_in_year(2024), 25);
+/// assert_eq!(days_in_year(2025), 12);
+/// ```
+pub const fn days_in_year(year: u16) -> u8 {
+    if year >= SHORT_EVENTS_SINCE {
+        SHORT_EVENT_DAYS
+    } else {
+        MAX_DAYS
+    }
+}
+
+/// An iterator that yields every day of the event of `year`, starting with the 1st.
+pub fn all_days(year: u16) -> AllDays {
+    AllDays::new(year)
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of the event of a year, starting with the 1st.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
-    #[allow(clippy::new_without_default)]
-    pub fn new() -> Self {
-        Self { current: 1 }
+    pub fn new(year: u16) -> Self {
+        Self {
+            current: 1,
+            last: days_in_year(year),
+        }
     }
 }
 
@@ -121,10 +168,10 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the last day.
         let day = Day(self.current);
         self.current += 1;
 
@@ -134,12 +181,138 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// A puzzle of an event, identified by the year of the event and the day.
+///
+/// # Display
+/// This value displays as the year followed by the day.
+///
+/// ```
+/// # use advent_of_code::template::{Day, PuzzleId};
+/// let puzzle = PuzzleId::new(2024, Day::new(8).unwrap());
+/// assert_eq!(puzzle.to_string(), "2024 day 08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: u16,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    /// Creates the puzzle of a day of the event of `year`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the day is not a day of the event, see [`PuzzleId::parse`] to validate user input.
+    pub const fn new(year: u16, day: Day) -> Self {
+        assert!(
+            day.0 <= days_in_year(year),
+            "the day is not a day of the event of the year"
+        );
+        Self { year, day }
+    }
+
+    /// Parses the puzzle of a day from a string, if it's a day of the event of `year`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the string is not a number, or not a day of the event.
+    pub fn parse(year: u16, day: &str) -> Result<Self, DayFromStrError> {
+        Day::parse_for_year(year, day).map(|day| Self::new(year, day))
+    }
+
+    /// Name of the solution binary, e.g. `2024_08`.
+    pub fn bin_name(self) -> String {
+        format!("{}_{}", self.year, self.day)
+    }
+
+    /// Path of the solution binary, e.g. `src/bin/2024_08.rs`.
+    pub fn bin_path(self) -> PathBuf {
+        PathBuf::from("src/bin").join(format!("{}.rs", self.bin_name()))
+    }
+
+    /// Directory the data of the event is stored in, e.g. `data/2024`.
+    pub fn data_dir(self) -> PathBuf {
+        data_dir(self.year)
+    }
+}
+
+#[cfg(feature = "today")]
+impl PuzzleId {
+    /// Returns the puzzle of the current day if it's a day of this year's event, `None` otherwise.
+    pub fn today() -> Option<Self> {
+        let offset = FixedOffset::east_opt(config::get().server_utc_offset * 3600)?;
+        let today = Utc::now().with_timezone(&offset);
+        if today.month() != 12 {
+            return None;
+        }
+
+        let year = u16::try_from(today.year()).ok()?;
+        let day = Day::for_year(year, u8::try_from(today.day()).ok()?)?;
+        Some(Self::new(year, day))
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} day {}", self.year, self.day)
+    }
+}
+
+/// Directory the data of an event is stored in, e.g. `data/2024`.
+/// The parent directory can be configured in [`config`](crate::template::config).
+pub fn data_dir(year: u16) -> PathBuf {
+    config::get().data_dir.join(year.to_string())
+}
+
+/// Parses the year from the name of a solution binary like `2024_08` in a const context.
+///
+/// # Panics
+///
+/// Panics if the name does not start with a year followed by an underscore.
+const fn year_from_bin_name(name: &str) -> u16 {
+    let bytes = name.as_bytes();
+    assert!(
+        bytes.len() > 5 && bytes[4] == b'_',
+        "solution binaries are named after the year and day, e.g. `src/bin/2024_08.rs`"
+    );
+
+    let mut year = 0;
+    let mut i = 0;
+    while i < 4 {
+        assert!(
+            bytes[i].is_ascii_digit(),
+            "solution binaries are named after the year and day, e.g. `src/bin/2024_08.rs`"
+        );
+        year = year * 10 + (bytes[i] - b'0') as u16;
+        i += 1;
+    }
+    year
+}
+
+/// Creates the [`PuzzleId`] of a solution binary in a const context, the year is parsed from its name.
+///
+/// # Panics
+///
+/// Panics if the name does not start with a year or the day is not part of the event of that year.
+// Not part of the public API
+#[doc(hidden)]
+pub const fn __puzzle_from_bin_name(name: &str, day: Day) -> PuzzleId {
+    let year = year_from_bin_name(name);
+    assert!(
+        day.into_inner() <= days_in_year(year),
+        "the day is not part of the event of the year in the binary name, see `days_in_year`"
+    );
+    PuzzleId::new(year, day)
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {{
         const _ASSERT: () = assert!(
-            $day != 0 && $day <= 25,
+            $day != 0 && $day <= $crate::template::MAX_DAYS,
             concat!(
                 "invalid day number `",
                 $day,
@@ -152,13 +325,13 @@ macro_rules! day {
 
 /* -------------------------------------------------------------------------- */
 
-#[cfg(feature = "test_lib")]
+#[cfg(all(test, feature = "test_lib"))]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, Day, PuzzleId};
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
+        let mut iter = all_days(2024);
 
         assert_eq!(iter.next(), Some(Day(1)));
         assert_eq!(iter.next(), Some(Day(2)));
@@ -187,6 +360,39 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn all_days_iterator_of_shorter_event() {
+        let days: Vec<Day> = all_days(2025).collect();
+
+        assert_eq!(days.len(), 12);
+        assert_eq!(days.last(), Some(&Day(12)));
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_puzzles_outside_of_the_event() {
+        let _ = PuzzleId::new(2025, Day(13));
+    }
+
+    #[test]
+    fn parses_puzzles_of_the_event() {
+        assert_eq!(
+            PuzzleId::parse(2025, "12").ok(),
+            Some(PuzzleId::new(2025, Day(12)))
+        );
+        assert!(PuzzleId::parse(2025, "13").is_err());
+        assert!(PuzzleId::parse(2024, "13").is_ok());
+    }
+
+    #[test]
+    fn parses_days_of_the_event() {
+        assert_eq!(Day::parse_for_year(2025, "12").ok(), Some(Day(12)));
+        assert_eq!(Day::parse_for_year(2024, "13").ok(), Some(Day(13)));
+
+        let err = Day::parse_for_year(2025, "13").unwrap_err();
+        assert_eq!(err.to_string(), "expecting a day number between 1 and 12");
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,315 @@
+/// Module that keeps an append-only history of benchmark runs.
+/// Every stored `cargo time` run is appended as one JSON document per line to the history file of its event.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::timings::{Timing, Timings};
+use crate::template::{
+    data_dir, PuzzleId, ANSI_BOLD, ANSI_GREEN, ANSI_ITALIC, ANSI_RED, ANSI_RESET,
+};
+
+static HISTORY_FILE_NAME: &str = "timings_history.jsonl";
+
+/// A single benchmark run.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Abbreviated hash of the checked out commit, suffixed with `-dirty` if there were local changes.
+    pub commit: Option<String>,
+    /// Cargo profile the solutions were built with.
+    pub profile: String,
+    pub timings: Timings,
+}
+
+impl HistoryEntry {
+    /// Create a history entry for timings that were just benched.
+    pub fn new(timings: &Timings, profile: &str) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        Self {
+            timestamp,
+            commit: get_commit(),
+            profile: profile.into(),
+            timings: timings.clone(),
+        }
+    }
+}
+
+/// Append an entry to the history file of an event.
+pub fn append(year: u16, entry: &HistoryEntry) -> Result<(), io::Error> {
+    let json = JsonValue::from(entry)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    fs::create_dir_all(data_dir(year))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(data_dir(year).join(HISTORY_FILE_NAME))?;
+
+    writeln!(file, "{json}")
+}
+
+/// Read all entries from the history file of an event, oldest first. Malformed lines are skipped.
+pub fn read(year: u16) -> Vec<HistoryEntry> {
+    fs::read_to_string(data_dir(year).join(HISTORY_FILE_NAME))
+        .unwrap_or_default()
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| HistoryEntry::try_from(line).ok())
+        .collect()
+}
+
+fn get_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|output| !output.stdout.is_empty());
+
+    Some(if is_dirty {
+        format!("{hash}-dirty")
+    } else {
+        hash
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Print how the timings of each part of a day evolved over all stored runs.
+pub fn print_history(puzzle: PuzzleId) {
+    let PuzzleId { year, day } = puzzle;
+    let rows: Vec<(HistoryEntry, Timing)> = read(year)
+        .into_iter()
+        .filter_map(|entry| {
+            let timing = entry.timings.data.iter().find(|t| t.day == day)?.clone();
+            Some((entry, timing))
+        })
+        .collect();
+
+    println!("{ANSI_BOLD}Day {day} history ({year}){ANSI_RESET}");
+    println!("------");
+
+    if rows.is_empty() {
+        println!(
+            "No stored benchmark runs for this day. Run `cargo time {day} --store` to record one."
+        );
+        return;
+    }
+
+    let mut previous: Option<&Timing> = None;
+
+    for (entry, timing) in &rows {
+        let parts = [1, 2]
+            .iter()
+            .map(|&part| format_part(timing, previous, part))
+            .collect::<Vec<_>>()
+            .join("  ");
+
+        println!(
+            "{}  {}  {ANSI_ITALIC}{}{ANSI_RESET}  {parts}",
+            format_timestamp(entry.timestamp),
+            entry.commit.as_deref().unwrap_or("-"),
+            entry.profile,
+        );
+
+        previous = Some(timing);
+    }
+}
+
+fn format_part(timing: &Timing, previous: Option<&Timing>, part: u8) -> String {
+    let value = match part {
+        1 => timing.part_1.as_deref(),
+        _ => timing.part_2.as_deref(),
+    };
+
+    let Some(value) = value else {
+        return format!("Part {part}: -");
+    };
+
+    let change = previous
+        .and_then(|previous| previous.part_nanos(part))
+        .zip(timing.part_nanos(part))
+        .filter(|(before, _)| *before > 0.0)
+        .map(|(before, after)| (after - before) / before * 100.0);
+
+    match change {
+        Some(change) if change.abs() >= 0.05 => {
+            let color = if change < 0.0 { ANSI_GREEN } else { ANSI_RED };
+            format!("Part {part}: {value} {color}({change:+.1}%){ANSI_RESET}")
+        }
+        _ => format!("Part {part}: {value}"),
+    }
+}
+
+/// Format a unix timestamp as `YYYY-MM-DD HH:MM` (UTC).
+fn format_timestamp(timestamp: u64) -> String {
+    let secs_of_day = timestamp % 86400;
+    let (year, month, day) = civil_from_days(timestamp / 86400);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        secs_of_day / 3600,
+        (secs_of_day % 3600) / 60
+    )
+}
+
+/// Convert days since the unix epoch into a (year, month, day) date.
+/// see: https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert("timings".into(), JsonValue::from(value.timings.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected entry.commit to be null or string.")?
+            .cloned();
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected entry.profile to be a string.")?
+            .clone();
+
+        let timings = json
+            .get("timings")
+            .ok_or("Expected entry to have key `timings`.")
+            .map_err(ToString::to_string)
+            .and_then(Timings::try_from)?;
+
+        Ok(HistoryEntry {
+            timestamp,
+            commit,
+            profile,
+            timings,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{format_timestamp, HistoryEntry};
+    use crate::{
+        day,
+        template::timings::{Timing, Timings},
+    };
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_733_454_720), "2024-12-06 03:12");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29 00:00");
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let entry = HistoryEntry {
+            timestamp: 1_733_454_720,
+            commit: Some("a1b2c3d".into()),
+            profile: "release".into(),
+            timings: Timings {
+                data: vec![Timing {
+                    day: day!(6),
+                    part_1: Some("470.1µs".into()),
+                    part_2: None,
+                    total_nanos: 470_100.0,
+                    timed_out: vec![2],
+                    ..Default::default()
+                }],
+            },
+        };
+
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        let parsed = HistoryEntry::try_from(line.as_str()).unwrap();
+
+        assert_eq!(parsed.timestamp, entry.timestamp);
+        assert_eq!(parsed.commit, entry.commit);
+        assert_eq!(parsed.profile, "release");
+        assert_eq!(parsed.timings.data.len(), 1);
+        assert_eq!(parsed.timings.data[0].part_1, Some("470.1µs".into()));
+        assert_eq!(parsed.timings.data[0].timed_out, vec![2]);
+    }
+
+    #[test]
+    fn handles_missing_commit() {
+        let line = r#"{ "timestamp": 0, "commit": null, "profile": "release", "timings": { "data": [] } }"#;
+        let parsed = HistoryEntry::try_from(line).unwrap();
+        assert_eq!(parsed.commit, None);
+    }
+}
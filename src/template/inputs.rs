@@ -0,0 +1,378 @@
+/// Module that locates the puzzle inputs a solution runs against.
+/// Besides the default input in `data/{year}/inputs/{day}.txt`, a day can have additional named inputs
+/// in `data/{year}/inputs/{day}/{name}.txt`, e.g. to compare the inputs of several accounts.
+/// A custom input can be passed with `--input <path>`, or `--input -` to read it from stdin.
+/// With `--example`, the examples in `data/{year}/examples` are run instead, preferring part-specific files like `03-2.txt`.
+use std::{
+    env, fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::{Day, PuzzleId};
+
+static INPUTS_DIR: &str = "inputs";
+static EXAMPLES_DIR: &str = "examples";
+
+/// Value of `--input` that reads the input from stdin.
+const STDIN_ARG: &str = "-";
+
+/// What an input is, determines which known answers apply to it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputKind {
+    /// The input of the account answers are submitted with, `data/inputs/{day}.txt`.
+    Default,
+    /// An additional input in `data/inputs/{day}/{name}.txt`.
+    Named(String),
+    /// An input passed with `--input`, it has no known answers.
+    Custom,
+    /// An example from `data/examples`.
+    Example,
+}
+
+/// Where an input is read from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    File(PathBuf),
+    Stdin,
+}
+
+/// A puzzle input of a day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Input {
+    pub kind: InputKind,
+    pub source: InputSource,
+    /// Only run this part against the input, `None` runs all parts.
+    pub part: Option<u8>,
+}
+
+impl Input {
+    /// The default input of a day, `data/{year}/inputs/{day}.txt`.
+    #[must_use]
+    pub fn default_for(puzzle: PuzzleId) -> Self {
+        Input {
+            kind: InputKind::Default,
+            source: InputSource::File(
+                puzzle
+                    .data_dir()
+                    .join(INPUTS_DIR)
+                    .join(format!("{}.txt", puzzle.day)),
+            ),
+            part: None,
+        }
+    }
+
+    /// A custom input passed as `--input <path>`, `-` reads from stdin.
+    #[must_use]
+    pub fn custom(arg: &str) -> Self {
+        Input {
+            kind: InputKind::Custom,
+            source: if arg == STDIN_ARG {
+                InputSource::Stdin
+            } else {
+                InputSource::File(PathBuf::from(arg))
+            },
+            part: None,
+        }
+    }
+
+    /// Returns `true` if `part` should run against the input.
+    #[must_use]
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+
+    /// Name of a named input, `None` for other inputs.
+    #[must_use]
+    pub fn name(&self) -> Option<&str> {
+        match &self.kind {
+            InputKind::Named(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Read the input to a string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input can not be read.
+    #[must_use]
+    pub fn read(&self) -> String {
+        match &self.source {
+            InputSource::File(path) => fs::read_to_string(path).expect("could not open input file"),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .expect("could not read input from stdin");
+                input
+            }
+        }
+    }
+
+    /// Human readable description of the input, used as header when not running the default input only.
+    #[must_use]
+    pub fn label(&self) -> String {
+        let source = match &self.source {
+            InputSource::File(path) => path.display().to_string(),
+            InputSource::Stdin => "stdin".to_string(),
+        };
+
+        match (&self.kind, self.part) {
+            (InputKind::Default, _) => format!("Input ({source})"),
+            (InputKind::Named(name), _) => format!("Input {name} ({source})"),
+            (InputKind::Custom, _) => {
+                format!("Custom input ({source}), known answers are not checked")
+            }
+            (InputKind::Example, None) => format!("Example ({source})"),
+            (InputKind::Example, Some(part)) => format!("Example for part {part} ({source})"),
+        }
+    }
+}
+
+/// List the named inputs of a day in `data/{year}/inputs/{day}/`, sorted by name.
+#[must_use]
+pub fn list_named_inputs(puzzle: PuzzleId) -> Vec<Input> {
+    list_named_inputs_in(&puzzle.data_dir().join(INPUTS_DIR), puzzle.day)
+}
+
+fn list_named_inputs_in(dir: &Path, day: Day) -> Vec<Input> {
+    let Ok(entries) = fs::read_dir(dir.join(day.to_string())) else {
+        return vec![];
+    };
+
+    let mut inputs: Vec<(String, PathBuf)> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "txt"))
+        .filter_map(|path| Some((path.file_stem()?.to_str()?.to_string(), path)))
+        .collect();
+
+    inputs.sort_unstable();
+    inputs
+        .into_iter()
+        .map(|(name, path)| Input {
+            kind: InputKind::Named(name),
+            source: InputSource::File(path),
+            part: None,
+        })
+        .collect()
+}
+
+/// All inputs of a day: the default input, if present, followed by the named inputs.
+#[must_use]
+pub fn all_inputs(puzzle: PuzzleId) -> Vec<Input> {
+    let default = Input::default_for(puzzle);
+
+    let mut inputs = vec![];
+    if matches!(&default.source, InputSource::File(path) if path.is_file()) {
+        inputs.push(default);
+    }
+    inputs.extend(list_named_inputs(puzzle));
+    inputs
+}
+
+/// The examples of a day. If a part-specific example like `03-2.txt` exists, each part runs
+/// against its own example, falling back to the shared `03.txt`.
+#[must_use]
+pub fn example_inputs(puzzle: PuzzleId) -> Vec<Input> {
+    example_inputs_in(&puzzle.data_dir().join(EXAMPLES_DIR), puzzle.day)
+}
+
+fn example_inputs_in(dir: &Path, day: Day) -> Vec<Input> {
+    let shared = dir.join(format!("{day}.txt"));
+    let example = |path: PathBuf, part: Option<u8>| Input {
+        kind: InputKind::Example,
+        source: InputSource::File(path),
+        part,
+    };
+
+    let part_specific: Vec<(u8, PathBuf)> = [1, 2]
+        .into_iter()
+        .map(|part| (part, dir.join(format!("{day}-{part}.txt"))))
+        .filter(|(_, path)| path.is_file())
+        .collect();
+
+    if part_specific.is_empty() {
+        return vec![example(shared, None)];
+    }
+
+    [1, 2]
+        .into_iter()
+        .filter_map(|part| {
+            let path = part_specific
+                .iter()
+                .find(|(p, _)| *p == part)
+                .map_or_else(|| shared.clone(), |(_, path)| path.clone());
+            path.is_file().then(|| example(path, Some(part)))
+        })
+        .collect()
+}
+
+/// Returns the value passed after `flag`, exits if the value is missing.
+fn arg_value<'a>(args: &'a [String], flag: &str) -> Option<&'a str> {
+    let index = args.iter().position(|x| x == flag)?;
+
+    if let Some(value) = args.get(index + 1) {
+        Some(value)
+    } else {
+        print_usage();
+        process::exit(1);
+    }
+}
+
+fn print_usage() {
+    eprintln!("Unexpected command-line input. Format: cargo solve 1 [--input <path> | --all-inputs | --example] [--part <part>]. `--input -` reads from stdin.");
+}
+
+/// The inputs selected by the arguments passed to the solution:
+///  1. `--input <path>` runs a custom input, `--input -` reads it from stdin.
+///  2. `--all-inputs` runs every input of the day.
+///  3. `--example` runs the examples of the day.
+///  4. otherwise, the default input is run.
+///
+/// `--part <part>` restricts the run to a single part.
+#[must_use]
+pub fn selected_inputs(puzzle: PuzzleId) -> Vec<Input> {
+    let args: Vec<String> = env::args().collect();
+    let all = args.iter().any(|x| x == "--all-inputs");
+    let example = args.iter().any(|x| x == "--example");
+    let custom = arg_value(&args, "--input");
+
+    if [all, example, custom.is_some()]
+        .iter()
+        .filter(|x| **x)
+        .count()
+        > 1
+    {
+        print_usage();
+        process::exit(1);
+    }
+
+    let inputs = match custom {
+        Some(arg) => vec![Input::custom(arg)],
+        None if all => all_inputs(puzzle),
+        None if example => example_inputs(puzzle),
+        None => vec![Input::default_for(puzzle)],
+    };
+
+    let Some(part) = arg_value(&args, "--part") else {
+        return inputs;
+    };
+
+    let Ok(part @ 1..=2) = part.parse::<u8>() else {
+        print_usage();
+        process::exit(1);
+    };
+
+    inputs
+        .into_iter()
+        .filter(|input| input.runs_part(part))
+        .map(|input| Input {
+            part: Some(part),
+            ..input
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::{env, fs, path::PathBuf};
+
+    use super::{example_inputs_in, list_named_inputs_in, Input, InputKind, InputSource};
+    use crate::{day, template::PuzzleId};
+
+    #[test]
+    fn lists_named_inputs_sorted() {
+        let dir = env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+        let day_dir = dir.join("06");
+        fs::create_dir_all(&day_dir).unwrap();
+        fs::write(day_dir.join("bob.txt"), "b").unwrap();
+        fs::write(day_dir.join("alice.txt"), "a").unwrap();
+        fs::write(day_dir.join("notes.md"), "ignored").unwrap();
+
+        let inputs = list_named_inputs_in(&dir, day!(6));
+        fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<Option<&str>> = inputs.iter().map(Input::name).collect();
+        assert_eq!(names, vec![Some("alice"), Some("bob")]);
+        assert_eq!(
+            inputs[0].source,
+            InputSource::File(day_dir.join("alice.txt"))
+        );
+    }
+
+    #[test]
+    fn handles_missing_input_dir() {
+        let dir = env::temp_dir().join("aoc-inputs-missing");
+        assert!(list_named_inputs_in(&dir, day!(6)).is_empty());
+    }
+
+    #[test]
+    fn parses_custom_inputs() {
+        assert_eq!(
+            Input::custom("-"),
+            Input {
+                kind: InputKind::Custom,
+                source: InputSource::Stdin,
+                part: None,
+            }
+        );
+        assert_eq!(
+            Input::custom("big.txt").source,
+            InputSource::File(PathBuf::from("big.txt"))
+        );
+    }
+
+    #[test]
+    fn labels_inputs() {
+        let input = Input {
+            kind: InputKind::Named("alice".into()),
+            source: InputSource::File(PathBuf::from("data/2024/inputs/06/alice.txt")),
+            part: None,
+        };
+        assert_eq!(input.label(), "Input alice (data/2024/inputs/06/alice.txt)");
+        assert_eq!(
+            Input::default_for(PuzzleId::new(2024, day!(6))).label(),
+            "Input (data/2024/inputs/06.txt)"
+        );
+        assert_eq!(
+            Input::custom("-").label(),
+            "Custom input (stdin), known answers are not checked"
+        );
+    }
+
+    #[test]
+    fn prefers_part_specific_examples() {
+        let dir = env::temp_dir().join(format!("aoc-examples-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+
+        fs::write(dir.join("03.txt"), "shared").unwrap();
+        let shared = example_inputs_in(&dir, day!(3));
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared[0].part, None);
+        assert_eq!(
+            shared[0].label(),
+            format!("Example ({})", dir.join("03.txt").display())
+        );
+
+        fs::write(dir.join("03-2.txt"), "part two").unwrap();
+        let specific = example_inputs_in(&dir, day!(3));
+        fs::remove_dir_all(&dir).unwrap();
+
+        let sources: Vec<(Option<u8>, InputSource)> = specific
+            .into_iter()
+            .map(|input| (input.part, input.source))
+            .collect();
+        assert_eq!(
+            sources,
+            vec![
+                (Some(1), InputSource::File(dir.join("03.txt"))),
+                (Some(2), InputSource::File(dir.join("03-2.txt"))),
+            ]
+        );
+    }
+}
@@ -0,0 +1,315 @@
+/// Module that renders private leaderboards as a terminal table.
+/// Leaderboards are read from the JSON API of the Advent of Code website,
+/// `/{year}/leaderboard/private/view/{id}.json`, or a local copy of it.
+use std::{collections::HashMap, str::FromStr};
+
+use tinyjson::JsonValue;
+
+use crate::template::{days_in_year, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Puzzles unlock at midnight EST, which is 05:00 UTC.
+const UNLOCK_HOUR_UTC: u64 = 5;
+
+/// A member of a private leaderboard.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Member {
+    pub id: u64,
+    /// `None` for anonymous users.
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u64,
+    /// Unix timestamps at which the parts of a day were solved, keyed by day.
+    pub completions: HashMap<u8, [Option<u64>; 2]>,
+}
+
+impl Member {
+    #[must_use]
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    fn completion(&self, day: u8, part: u8) -> Option<u64> {
+        self.completions.get(&day)?[usize::from(part - 1)]
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Leaderboard {
+    pub year: u16,
+    pub members: Vec<Member>,
+}
+
+impl Leaderboard {
+    /// The last day any member solved a part of.
+    #[must_use]
+    pub fn latest_day(&self) -> Option<Day> {
+        self.members
+            .iter()
+            .flat_map(|member| member.completions.keys())
+            .max()
+            .and_then(|day| Day::for_year(self.year, *day))
+    }
+
+    /// Render the leaderboard as a table, ranked by local score. `day` selects the day whose
+    /// completion times are shown, relative to the unlock of its puzzle.
+    #[must_use]
+    pub fn render(&self, day: Option<Day>) -> String {
+        let mut members: Vec<&Member> = self.members.iter().collect();
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.display_name().cmp(&b.display_name()))
+        });
+
+        let name_width = members
+            .iter()
+            .map(|member| member.display_name().chars().count())
+            .max()
+            .unwrap_or_default()
+            .max(4);
+
+        let last_day = days_in_year(self.year);
+        let days: String = (1..=last_day).map(|d| char::from(b'0' + d / 10)).collect();
+        let days_ones: String = (1..=last_day).map(|d| char::from(b'0' + d % 10)).collect();
+        let day_header = day
+            .map(|_| format!("  {:>9}  {:>9}", "Part 1", "Part 2"))
+            .unwrap_or_default();
+
+        let mut lines = vec![
+            format!("{ANSI_BOLD}{} Private leaderboard{ANSI_RESET}", self.year),
+            format!(
+                "{:>3}  {:name_width$}  {:>5}  {:>5}  {days}",
+                "", "", "", ""
+            ),
+            format!(
+                "{:>3}  {:name_width$}  {:>5}  {:>5}  {days_ones}{day_header}",
+                "#", "Name", "Score", "Stars"
+            ),
+        ];
+
+        for (rank, member) in members.iter().enumerate() {
+            let grid: String = (1..=last_day)
+                .map(
+                    |d| match (member.completion(d, 1), member.completion(d, 2)) {
+                        (Some(_), Some(_)) => '★',
+                        (Some(_), None) => '☆',
+                        _ => '·',
+                    },
+                )
+                .collect();
+
+            let times = day
+                .map(|day| {
+                    let unlock = unlock_timestamp(self.year, day);
+                    let [part_1, part_2] = [1, 2].map(|part| {
+                        member
+                            .completion(day.into_inner(), part)
+                            .map_or("-".into(), |ts| format_relative(ts.saturating_sub(unlock)))
+                    });
+                    format!("  {part_1:>9}  {part_2:>9}")
+                })
+                .unwrap_or_default();
+
+            lines.push(format!(
+                "{:>3}  {:name_width$}  {:>5}  {:>5}  {grid}{times}",
+                rank + 1,
+                member.display_name(),
+                member.local_score,
+                member.stars,
+            ));
+        }
+
+        if let Some(day) = day {
+            lines.push(String::new());
+            lines.push(format!(
+                "{ANSI_ITALIC}Completion times of day {day} are relative to the unlock of its puzzle.{ANSI_RESET}"
+            ));
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Unix timestamp at which the puzzle of a day unlocks.
+fn unlock_timestamp(year: u16, day: Day) -> u64 {
+    days_from_civil(u64::from(year), 12, u64::from(day.into_inner())) * 86400
+        + UNLOCK_HOUR_UTC * 3600
+}
+
+/// Format a duration in seconds as `HH:MM:SS`, hours are not wrapped at a day.
+fn format_relative(secs: u64) -> String {
+    format!(
+        "{:02}:{:02}:{:02}",
+        secs / 3600,
+        (secs % 3600) / 60,
+        secs % 60
+    )
+}
+
+/// Convert a date into days since the unix epoch, only valid for dates after the epoch.
+/// see: https://howardhinnant.github.io/date_algorithms.html#days_from_civil
+fn days_from_civil(year: u64, month: u64, day: u64) -> u64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year / 400;
+    let yoe = year - era * 400;
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<&str> for Leaderboard {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+        Leaderboard::try_from(&json)
+    }
+}
+
+impl TryFrom<&JsonValue> for Leaderboard {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected leaderboard to be a JSON object.")?;
+
+        let year = json
+            .get("event")
+            .and_then(|v| v.get::<String>())
+            .and_then(|event| event.parse().ok())
+            .ok_or("Expected leaderboard.event to be a year.")?;
+
+        let mut members = json
+            .get("members")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected leaderboard.members to be an object.")?
+            .values()
+            .map(Member::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        members.sort_by_key(|member| member.id);
+
+        Ok(Leaderboard { year, members })
+    }
+}
+
+impl TryFrom<&JsonValue> for Member {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected member to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number =
+            |value: Option<&JsonValue>| value.and_then(|v| v.get::<f64>()).map(|x| *x as u64);
+
+        let mut completions = HashMap::new();
+
+        let days = json
+            .get("completion_day_level")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected member.completion_day_level to be an object.")?;
+
+        for (day, parts) in days {
+            let day: u8 = day.parse().map_err(|_| {
+                format!("Expected {day} in member.completion_day_level to be a day.")
+            })?;
+            let parts = parts
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("Expected the parts of a day to be an object.")?;
+
+            let completion = |part: &str| {
+                number(
+                    parts
+                        .get(part)
+                        .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+                        .and_then(|part| part.get("get_star_ts")),
+                )
+            };
+
+            completions.insert(day, [completion("1"), completion("2")]);
+        }
+
+        Ok(Member {
+            id: number(json.get("id")).ok_or("Expected member.id to be a number.")?,
+            name: json.get("name").and_then(|v| v.get::<String>()).cloned(),
+            local_score: number(json.get("local_score"))
+                .ok_or("Expected member.local_score to be a number.")?,
+            stars: number(json.get("stars")).ok_or("Expected member.stars to be a number.")?,
+            completions,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{days_from_civil, format_relative, unlock_timestamp, Leaderboard};
+    use crate::day;
+
+    const LEADERBOARD: &str = r#"{
+        "event": "2024",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "alice", "stars": 3, "local_score": 12, "global_score": 0, "last_star_ts": 1733044000,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1733029800, "star_index": 0 }, "2": { "get_star_ts": 1733030100, "star_index": 1 } },
+                    "2": { "1": { "get_star_ts": 1733121000, "star_index": 2 } }
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 1, "local_score": 2, "global_score": 0, "last_star_ts": 0,
+                "completion_day_level": {
+                    "2": { "1": { "get_star_ts": 1733202000, "star_index": 3 } }
+                }
+            }
+        }
+    }"#;
+
+    #[test]
+    fn parses_leaderboards() {
+        let leaderboard = Leaderboard::try_from(LEADERBOARD).unwrap();
+
+        assert_eq!(leaderboard.year, 2024);
+        assert_eq!(leaderboard.members.len(), 2);
+        assert_eq!(leaderboard.members[0].name.as_deref(), Some("alice"));
+        assert_eq!(
+            leaderboard.members[0].completions[&1],
+            [Some(1_733_029_800), Some(1_733_030_100)]
+        );
+        assert_eq!(leaderboard.members[1].display_name(), "(anonymous user #2)");
+        assert_eq!(leaderboard.latest_day(), Some(day!(2)));
+    }
+
+    #[test]
+    fn computes_unlock_times() {
+        assert_eq!(days_from_civil(1970, 1, 1), 0);
+        // 2024-12-01T05:00:00Z
+        assert_eq!(unlock_timestamp(2024, day!(1)), 1_733_029_200);
+        assert_eq!(format_relative(600), "00:10:00");
+        assert_eq!(format_relative(90_061), "25:01:01");
+    }
+
+    #[test]
+    fn renders_leaderboards() {
+        let leaderboard = Leaderboard::try_from(LEADERBOARD).unwrap();
+        let rendered = leaderboard.render(Some(day!(1)));
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert!(lines[3].starts_with("  1  alice                   12      3  ★☆·"));
+        assert!(lines[3].ends_with("   00:10:00   00:15:00"));
+        assert!(lines[4].starts_with("  2  (anonymous user #2)      2      1  ·☆·"));
+        assert!(lines[4].ends_with("          -          -"));
+    }
+}
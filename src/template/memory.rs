@@ -0,0 +1,294 @@
+/// Module that measures heap usage of solution parts.
+/// Memory is only measured when either the `dhat-heap` feature (e.g. via `cargo solve <day> --dhat`)
+/// or the cheaper `count-allocs` feature (e.g. via `cargo time --count-allocs`) is enabled.
+use std::{collections::HashMap, fmt::Display};
+
+use tinyjson::JsonValue;
+
+/// Heap usage of a single solve call.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Maximum number of bytes allocated at the same time.
+    pub peak_bytes: u64,
+    /// Number of bytes allocated over the whole call.
+    pub total_bytes: u64,
+    /// Number of allocations over the whole call.
+    pub allocations: u64,
+}
+
+const MEMORY_PREFIX: &str = "[mem: ";
+
+/// Run `func` and measure its heap usage, if a memory profiling feature is enabled.
+#[cfg(feature = "dhat-heap")]
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<MemoryStats>) {
+    let _profiler = dhat::Profiler::new_heap();
+    let result = func();
+    let stats = dhat::HeapStats::get();
+
+    (
+        result,
+        Some(MemoryStats {
+            peak_bytes: stats.max_bytes as u64,
+            total_bytes: stats.total_bytes,
+            allocations: stats.total_blocks,
+        }),
+    )
+}
+
+/// Run `func` and measure its heap usage, if a memory profiling feature is enabled.
+#[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<MemoryStats>) {
+    let baseline = counting::start();
+    let result = func();
+    (result, Some(counting::stats_since(&baseline)))
+}
+
+/// Run `func` and measure its heap usage, if a memory profiling feature is enabled.
+#[cfg(not(any(feature = "dhat-heap", feature = "count-allocs")))]
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<MemoryStats>) {
+    (func(), None)
+}
+
+#[cfg(feature = "count-allocs")]
+pub use counting::CountingAlloc;
+
+/// A global allocator that counts allocations, it is cheap enough to stay enabled while benching.
+#[cfg(feature = "count-allocs")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use super::MemoryStats;
+
+    static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    static TOTAL_BYTES: AtomicU64 = AtomicU64::new(0);
+    static CURRENT_BYTES: AtomicU64 = AtomicU64::new(0);
+    static PEAK_BYTES: AtomicU64 = AtomicU64::new(0);
+
+    /// Wraps the system allocator, installed by the `solution!` macro.
+    pub struct CountingAlloc;
+
+    fn record_alloc(size: usize) {
+        let size = size as u64;
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        TOTAL_BYTES.fetch_add(size, Ordering::Relaxed);
+        let current = CURRENT_BYTES.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn record_dealloc(size: usize) {
+        CURRENT_BYTES.fetch_sub(size as u64, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAlloc {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            record_dealloc(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                // NOTE: like dhat, count a reallocation as freeing the old and allocating a new block.
+                record_dealloc(layout.size());
+                record_alloc(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    /// Counter values at the start of a measurement.
+    pub struct Baseline {
+        allocations: u64,
+        total_bytes: u64,
+        current_bytes: u64,
+    }
+
+    /// Start a measurement, resetting the peak to the currently allocated bytes.
+    pub fn start() -> Baseline {
+        let current_bytes = CURRENT_BYTES.load(Ordering::Relaxed);
+        PEAK_BYTES.store(current_bytes, Ordering::Relaxed);
+
+        Baseline {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            total_bytes: TOTAL_BYTES.load(Ordering::Relaxed),
+            current_bytes,
+        }
+    }
+
+    pub fn stats_since(baseline: &Baseline) -> MemoryStats {
+        MemoryStats {
+            peak_bytes: PEAK_BYTES
+                .load(Ordering::Relaxed)
+                .saturating_sub(baseline.current_bytes),
+            total_bytes: TOTAL_BYTES.load(Ordering::Relaxed) - baseline.total_bytes,
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - baseline.allocations,
+        }
+    }
+}
+
+impl MemoryStats {
+    /// Parse memory stats that were appended to a result line by [`MemoryStats`]'s `Display` impl.
+    pub fn parse_from_line(line: &str) -> Option<Self> {
+        let stats = line.split(MEMORY_PREFIX).nth(1)?.split(']').next()?;
+        let mut values = stats.split(", ").map(|value| {
+            value
+                .split(' ')
+                .next()?
+                .replace(',', "")
+                .parse::<u64>()
+                .ok()
+        });
+
+        Some(MemoryStats {
+            peak_bytes: values.next()??,
+            total_bytes: values.next()??,
+            allocations: values.next()??,
+        })
+    }
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{MEMORY_PREFIX}{} B peak, {} B total, {} allocs]",
+            group_digits(self.peak_bytes),
+            group_digits(self.total_bytes),
+            group_digits(self.allocations)
+        )
+    }
+}
+
+/// Format a number with `,` as thousands separator.
+fn group_digits(value: u64) -> String {
+    let digits = value.to_string();
+    let mut grouped = String::with_capacity(digits.len() + digits.len() / 3);
+
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            grouped.push(',');
+        }
+        grouped.push(c);
+    }
+
+    grouped
+}
+
+/// Format a byte count with a binary unit, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&MemoryStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
+        );
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory stats to be a JSON object.")?;
+
+        let get = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|v| *v as u64)
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(MemoryStats {
+            peak_bytes: get("peak_bytes")?,
+            total_bytes: get("total_bytes")?,
+            allocations: get("allocations")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{format_bytes, MemoryStats};
+
+    #[test]
+    fn roundtrips_result_lines() {
+        let stats = MemoryStats {
+            peak_bytes: 1_234_567,
+            total_bytes: 89,
+            allocations: 1000,
+        };
+        let line = format!("Part 1: 42 (1.2ms) {stats}");
+        assert!(line.ends_with("[mem: 1,234,567 B peak, 89 B total, 1,000 allocs]"));
+        assert_eq!(MemoryStats::parse_from_line(&line), Some(stats));
+    }
+
+    #[test]
+    fn ignores_lines_without_stats() {
+        assert_eq!(
+            MemoryStats::parse_from_line("Part 1: 42 (1.2ms @ 10 samples)"),
+            None
+        );
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+    }
+}
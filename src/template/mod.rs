@@ -1,42 +1,63 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod answers;
+pub mod aoc_client;
 pub mod commands;
+pub mod config;
+pub mod inputs;
+pub mod memory;
 pub mod runner;
+pub mod spans;
 
+pub use compare::DEFAULT_THRESHOLD_PERCENT;
 pub use day::*;
+pub use history::print_history;
 
+mod compare;
 mod day;
+mod history;
+pub mod leaderboard;
+mod puzzle;
 mod readme_benchmarks;
+pub mod report;
 mod run_multi;
+pub mod status;
+mod submissions;
 mod timings;
+mod watch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_RED: &str = "\x1b[31m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file to a string. E.g. like `data/2024/examples/01.txt`.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join(puzzle.data_dir())
+        .join(folder)
+        .join(format!("{}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(puzzle.data_dir())
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+/// The year of the puzzle is taken from the name of the binary, e.g. `src/bin/2024_01.rs`.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
 #[macro_export]
@@ -52,17 +73,46 @@ macro_rules! solution {
     };
 
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
-        /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        /// The current puzzle.
+        const DAY: $crate::template::PuzzleId =
+            $crate::template::__puzzle_from_bin_name(env!("CARGO_BIN_NAME"), $crate::day!($day));
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(all(feature = "count-allocs", not(feature = "dhat-heap")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::memory::CountingAlloc =
+            $crate::template::memory::CountingAlloc;
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let inputs = $crate::template::inputs::selected_inputs(DAY);
+            let mut has_failures = false;
+            for (i, input_file) in inputs.iter().enumerate() {
+                if inputs.len() > 1 || input_file.kind != $crate::template::inputs::InputKind::Default {
+                    print_input_header(input_file, i > 0);
+                }
+                // NOTE: the input is leaked so parts can run on their own thread and be abandoned on timeout.
+                let input: &'static str = input_file.read().leak();
+                $(
+                    if input_file.runs_part($part) {
+                        let status = run_part($func, input, DAY, &input_file.kind, $part);
+                        if let PartStatus::TimedOut(_) = status {
+                            exit_after_timeout();
+                        }
+                        has_failures |= status.is_failure();
+                    }
+                )*
+            }
+            if inputs.is_empty() {
+                eprintln!("No inputs found for {DAY}.");
+                has_failures = true;
+            }
+            if has_failures {
+                std::process::exit(1);
+            }
         }
     };
 }
@@ -0,0 +1,328 @@
+//! Module that converts puzzle pages of the Advent of Code website into markdown,
+//! and parses that markdown for example inputs and their answers.
+//! The conversion only covers the handful of tags used in puzzle descriptions.
+
+/// Extract the puzzle descriptions from a puzzle page and convert them to markdown.
+/// Returns `None` if the page does not contain a description.
+#[must_use]
+pub fn html_to_markdown(html: &str) -> Option<String> {
+    let articles = extract_articles(html);
+
+    if articles.is_empty() {
+        return None;
+    }
+
+    let markdown = articles
+        .iter()
+        .map(|article| convert(article))
+        .collect::<Vec<_>>()
+        .join("\n\n");
+
+    Some(format!("{}\n", markdown.trim()))
+}
+
+/// Returns the contents of all `<article class="day-desc">` elements, one per solved part.
+fn extract_articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let after_start = &rest[start..];
+        let Some(open_end) = after_start.find('>') else {
+            break;
+        };
+        let Some(close) = after_start.find("</article>") else {
+            break;
+        };
+
+        if after_start[..open_end].contains("day-desc") {
+            articles.push(&after_start[open_end + 1..close]);
+        }
+
+        rest = &after_start[close + "</article>".len()..];
+    }
+
+    articles
+}
+
+fn convert(html: &str) -> String {
+    let mut markdown = String::new();
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(tag_start) = rest.find('<') else {
+            markdown.push_str(&decode_entities(rest));
+            break;
+        };
+
+        markdown.push_str(&decode_entities(&rest[..tag_start]));
+
+        let Some(tag_len) = rest[tag_start..].find('>') else {
+            break;
+        };
+        let tag = &rest[tag_start + 1..tag_start + tag_len];
+        rest = &rest[tag_start + tag_len + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default();
+
+        match (name, is_closing) {
+            ("h2", false) => markdown.push_str("## "),
+            ("h2" | "p" | "ul", true) => markdown.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                markdown.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+            }
+            // code and emphasis inside of code blocks are not rendered by markdown.
+            ("code" | "em", _) if in_pre => {}
+            ("code", _) => markdown.push('`'),
+            ("em", _) => markdown.push('*'),
+            ("li", false) => markdown.push_str("- "),
+            ("li", true) | ("br", _) => markdown.push('\n'),
+            _ => {}
+        }
+    }
+
+    collapse_blank_lines(&markdown)
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+fn collapse_blank_lines(text: &str) -> String {
+    let mut result = String::new();
+    let mut blank_lines = 0;
+
+    for line in text.lines() {
+        if line.trim().is_empty() {
+            blank_lines += 1;
+            if blank_lines > 1 {
+                continue;
+            }
+        } else {
+            blank_lines = 0;
+        }
+        result.push_str(line.trim_end());
+        result.push('\n');
+    }
+
+    result.trim().to_string()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Code blocks and answer candidates of one part of a puzzle description.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PuzzlePart {
+    pub part: u8,
+    pub code_blocks: Vec<String>,
+    /// Emphasized inline code, the way puzzle descriptions highlight the answers of examples.
+    pub answers: Vec<String>,
+}
+
+/// Parse a puzzle description converted by [`html_to_markdown`] into its parts.
+/// The second part starts at the "Part Two" heading, which is only present once part one is solved.
+#[must_use]
+pub fn parse_markdown(markdown: &str) -> Vec<PuzzlePart> {
+    let mut parts = vec![PuzzlePart {
+        part: 1,
+        ..PuzzlePart::default()
+    }];
+    let mut code_block: Option<Vec<&str>> = None;
+
+    for line in markdown.lines() {
+        let current = parts.last_mut().unwrap();
+
+        if line.starts_with("```") {
+            match code_block.take() {
+                Some(lines) => current.code_blocks.push(lines.join("\n")),
+                None => code_block = Some(vec![]),
+            }
+        } else if let Some(lines) = &mut code_block {
+            lines.push(line);
+        } else if line.starts_with("## ") && line.contains("Part Two") {
+            parts.push(PuzzlePart {
+                part: 2,
+                ..PuzzlePart::default()
+            });
+        } else {
+            for answer in emphasized_code(line) {
+                if !current.answers.contains(&answer) {
+                    current.answers.push(answer);
+                }
+            }
+        }
+    }
+
+    parts
+}
+
+/// The part two section of a puzzle description, starting at its heading.
+/// Returns `None` if part two is not unlocked yet.
+#[must_use]
+pub fn part_two_section(markdown: &str) -> Option<&str> {
+    let mut offset = 0;
+
+    for line in markdown.split_inclusive('\n') {
+        if line.starts_with("## ") && line.contains("Part Two") {
+            return Some(&markdown[offset..]);
+        }
+        offset += line.len();
+    }
+
+    None
+}
+
+/// Find inline code that is emphasized, written as either `` `*x*` `` or `` *`x`* ``.
+fn emphasized_code(line: &str) -> Vec<String> {
+    let mut found = vec![];
+    let mut rest = line;
+
+    while !rest.is_empty() {
+        let close = if rest.starts_with("`*") {
+            "*`"
+        } else if rest.starts_with("*`") {
+            "`*"
+        } else {
+            let mut chars = rest.chars();
+            chars.next();
+            rest = chars.as_str();
+            continue;
+        };
+
+        let Some(end) = rest[2..].find(close) else {
+            break;
+        };
+        found.push(rest[2..2 + end].to_string());
+        rest = &rest[2 + end + 2..];
+    }
+
+    found
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{html_to_markdown, parse_markdown, part_two_section, PuzzlePart};
+
+    #[test]
+    fn converts_puzzle_descriptions() {
+        let html = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>The lists are <em>not</em> very similar &amp; short:</p>
+<pre><code>3   4
+<em>4</em>   3
+</code></pre>
+<ul><li>Pair up <code>1</code>.</li><li>Sum it.</li></ul>
+<p>What is the <em>total distance</em>?</p>
+</article>
+<p>Your puzzle answer was <code>42</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Now <code>x &lt; y</code>.</p></article>
+</main></body></html>"#;
+
+        assert_eq!(
+            html_to_markdown(html).unwrap(),
+            "## --- Day 1: Historian Hysteria ---
+
+The lists are *not* very similar & short:
+
+```
+3   4
+4   3
+```
+
+- Pair up `1`.
+- Sum it.
+
+What is the *total distance*?
+
+## --- Part Two ---
+
+Now `x < y`.
+"
+        );
+    }
+
+    #[test]
+    fn handles_pages_without_description() {
+        assert_eq!(html_to_markdown("<html><p>404 Not Found</p></html>"), None);
+    }
+
+    #[test]
+    fn parses_examples_and_answers() {
+        let markdown = "## --- Day 1: Historian Hysteria ---
+
+For example:
+
+```
+3   4
+4   3
+```
+
+The distance is `*2*`, between *`3`* and *`4`*. This is *not* an answer.
+
+## --- Part Two ---
+
+```
+1 2
+```
+
+Now it is `*31*`.
+";
+
+        assert_eq!(
+            parse_markdown(markdown),
+            vec![
+                PuzzlePart {
+                    part: 1,
+                    code_blocks: vec!["3   4\n4   3".into()],
+                    answers: vec!["2".into(), "3".into(), "4".into()],
+                },
+                PuzzlePart {
+                    part: 2,
+                    code_blocks: vec!["1 2".into()],
+                    answers: vec!["31".into()],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn converts_emphasized_code_to_candidates() {
+        let html = r#"<article class="day-desc"><p>Total: <code><em>11</em></code>.</p><pre><code>1
+</code></pre></article>"#;
+
+        let parts = parse_markdown(&html_to_markdown(html).unwrap());
+        assert_eq!(parts[0].answers, vec!["11".to_string()]);
+        assert_eq!(parts[0].code_blocks, vec!["1".to_string()]);
+    }
+
+    #[test]
+    fn finds_part_two_section() {
+        let markdown = "## --- Day 1 ---\n\nFirst.\n\n## --- Part Two ---\n\nSecond.\n";
+        assert_eq!(
+            part_two_section(markdown),
+            Some("## --- Part Two ---\n\nSecond.\n")
+        );
+        assert_eq!(part_two_section("## --- Day 1 ---\n\nFirst.\n"), None);
+    }
+}
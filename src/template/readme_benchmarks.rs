@@ -1,11 +1,21 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+///
+/// Every event has its own table, selected by the `year` option of its opening marker.
+/// The table can be configured with further options in the opening marker, e.g.
+/// `<!--- benchmarking table year=2024 columns=part_1,part_2,share,bar sort=cost highlight=3 subtotals --->`.
+/// The readme, the text of the markers and default options are set in `aoc.toml`.
+use std::{fs, io, str::FromStr, time::Duration};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::config::{self, Config};
+use crate::template::memory::{format_bytes, MemoryStats};
+use crate::template::timings::{Timing, Timings};
+use crate::template::{Day, PuzzleId};
 
-static MARKER: &str = "<!--- benchmarking table --->";
+static MARKER_END: &str = "--->";
+
+/// Width of the relative bar column in characters.
+const BAR_WIDTH: f64 = 16.0;
 
 #[allow(dead_code)]
 #[derive(Debug)]
@@ -20,87 +30,469 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+/// Markers and default options of the benchmark tables.
+pub struct TableConfig {
+    /// Text of the markers, e.g. `benchmarking table`.
+    marker: String,
+    /// Options applied to every table, before the options of its marker.
+    defaults: String,
+    /// Columns of tables that don't set `columns` in their options.
+    columns: Option<Vec<Column>>,
+}
+
+impl TableConfig {
+    fn from_config(config: &Config) -> Result<Self, Error> {
+        let columns = config
+            .benchmark_columns
+            .as_ref()
+            .map(|columns| columns.iter().map(|c| c.parse()).collect())
+            .transpose()?;
+
+        Ok(TableConfig {
+            marker: config.benchmark_marker.clone(),
+            defaults: config.benchmark_table.clone(),
+            columns,
+        })
+    }
+
+    /// Beginning of every marker, e.g. `<!--- benchmarking table`.
+    fn marker_start(&self) -> String {
+        format!("<!--- {}", self.marker)
+    }
+
+    /// The closing marker of a table, e.g. `<!--- benchmarking table --->`.
+    fn closing_marker(&self) -> String {
+        format!("<!--- {} {MARKER_END}", self.marker)
+    }
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
+    start_marker: String,
+    end_marker: String,
+}
+
+/// A column of the benchmark table, in addition to the day column.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Part1,
+    Part2,
+    /// Duration of the top-level `parse` spans of both parts, recorded with `--spans`.
+    Parse,
+    /// Combined duration of both parts.
+    Total,
+    /// Share of the day in the total duration of all days.
+    Share,
+    /// Peak heap size of both parts, rendered as two columns.
+    Memory,
+    /// Bar showing the day's duration relative to the slowest day.
+    Bar,
+}
+
+impl FromStr for Column {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "parse" => Ok(Column::Parse),
+            "total" => Ok(Column::Total),
+            "share" => Ok(Column::Share),
+            "memory" => Ok(Column::Memory),
+            "bar" => Ok(Column::Bar),
+            s => Err(Error::Parser(format!(
+                "Unknown benchmark table column `{s}`."
+            ))),
+        }
+    }
+}
+
+impl Column {
+    fn headers(self) -> &'static [&'static str] {
+        match self {
+            Column::Part1 => &["Part 1"],
+            Column::Part2 => &["Part 2"],
+            Column::Parse => &["Parse"],
+            Column::Total => &["Total"],
+            Column::Share => &["Share"],
+            Column::Memory => &["Part 1 Memory", "Part 2 Memory"],
+            Column::Bar => &["Relative"],
+        }
+    }
+}
+
+/// Options that control how the benchmark table is rendered.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TableOptions {
+    /// Event the table shows the timings of.
+    pub year: Option<u16>,
+    /// Columns to render. When not set, parts and (if measured) memory are rendered.
+    pub columns: Option<Vec<Column>>,
+    /// Sort days by their combined duration, slowest first.
+    pub sort_by_cost: bool,
+    /// Number of slowest days to highlight.
+    pub highlight: usize,
+    /// Render a row with the sum of each column.
+    pub subtotals: bool,
+}
+
+impl FromStr for TableOptions {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut options = TableOptions::default();
+
+        for option in s.split_whitespace() {
+            let (key, value) = option.split_once('=').unwrap_or((option, ""));
+
+            match key {
+                "year" => {
+                    options.year = Some(value.parse().map_err(|_| {
+                        Error::Parser(format!("Expected `year` to be a year, got `{value}`."))
+                    })?);
+                }
+                "columns" => {
+                    options.columns = Some(
+                        value
+                            .split(',')
+                            .map(Column::from_str)
+                            .collect::<Result<_, _>>()?,
+                    );
+                }
+                "sort" => match value {
+                    "cost" => options.sort_by_cost = true,
+                    "day" => options.sort_by_cost = false,
+                    _ => {
+                        return Err(Error::Parser(format!(
+                            "Unknown sort order `{value}`, expecting `day` or `cost`."
+                        )))
+                    }
+                },
+                "highlight" => {
+                    options.highlight = value.parse().map_err(|_| {
+                        Error::Parser(format!(
+                            "Expected `highlight` to be a number, got `{value}`."
+                        ))
+                    })?;
+                }
+                "subtotals" => options.subtotals = true,
+                _ => {
+                    return Err(Error::Parser(format!(
+                        "Unknown benchmark table option `{key}`."
+                    )))
+                }
+            }
+        }
+
+        Ok(options)
+    }
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./src/bin/{}.rs", puzzle.bin_name())
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, config: &TableConfig, year: u16) -> Result<TablePosition, Error> {
+    // markers are `<!--- benchmarking table --->`, the opening one carries the year and table options.
+    let marker_start = config.marker_start();
+    let matches: Vec<(usize, &str)> = readme
+        .match_indices(&marker_start)
+        .filter_map(|(pos, _)| {
+            let len = readme[pos..].find(MARKER_END)? + MARKER_END.len();
+            Some((pos, &readme[pos..pos + len]))
+        })
+        .collect();
 
-    if matches.len() > 2 {
+    if !matches.len().is_multiple_of(2) {
         return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
+            "Unbalanced benchmark table markers in README, every table needs an opening and a closing marker.".into(),
         ));
     }
 
-    let pos_start = matches
-        .first()
-        .map(|m| m.0)
-        .ok_or_else(|| Error::Parser("Could not find table start position.".into()))?;
+    for table in matches.chunks(2) {
+        let [(pos_start, start_marker), (pos_end, end_marker)] = table else {
+            continue;
+        };
 
-    let pos_end = matches
-        .last()
-        .map(|m| m.0 + m.1.len())
-        .ok_or_else(|| Error::Parser("Could not find table end position.".into()))?;
+        if parse_marker_options(start_marker, config)?.year == Some(year) {
+            return Ok(TablePosition {
+                pos_start: *pos_start,
+                pos_end: pos_end + end_marker.len(),
+                start_marker: (*start_marker).to_string(),
+                end_marker: (*end_marker).to_string(),
+            });
+        }
+    }
 
-    Ok(TablePosition { pos_start, pos_end })
+    Err(Error::Parser(format!(
+        "Could not find the benchmark table of {year}, expected a `{marker_start} year={year} {MARKER_END}` marker followed by a `{}` marker.",
+        config.closing_marker()
+    )))
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Parse the options of an opening marker, on top of the default options.
+fn parse_marker_options(marker: &str, config: &TableConfig) -> Result<TableOptions, Error> {
+    let options = marker
+        .trim_start_matches(&config.marker_start())
+        .trim_end_matches(MARKER_END);
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut options: TableOptions = format!("{} {options}", config.defaults).parse()?;
+    if options.columns.is_none() {
+        options.columns.clone_from(&config.columns);
+    }
+
+    Ok(options)
+}
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+fn render_bar(value: f64, max: f64) -> String {
+    const PARTIAL_BLOCKS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+    if max <= 0.0 {
+        return String::new();
+    }
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let eighths = (value / max * BAR_WIDTH * 8.0).round() as usize;
+    format!("{}{}", "█".repeat(eighths / 8), PARTIAL_BLOCKS[eighths % 8])
+}
+
+fn format_part(timing: &Timing, part: u8) -> String {
+    let value = match part {
+        1 => timing.part_1.clone(),
+        _ => timing.part_2.clone(),
+    };
+
+    value.unwrap_or_else(|| {
+        if timing.timed_out.contains(&part) {
+            "timeout".into()
+        } else {
+            "-".into()
+        }
+    })
+}
+
+/// Duration of the top-level `parse` spans of both parts, `None` if no such span was recorded.
+fn parse_nanos(timing: &Timing) -> Option<f64> {
+    timing
+        .part_1_spans
+        .iter()
+        .chain(&timing.part_2_spans)
+        .filter(|span| span.depth == 0 && span.name == "parse")
+        .map(|span| span.nanos)
+        .reduce(|a, b| a + b)
+}
+
+fn format_memory(memory: Option<MemoryStats>) -> String {
+    memory.map_or_else(|| "-".into(), |m| format_bytes(m.peak_bytes))
+}
+
+fn render_cells(column: Column, timing: &Timing, sum_nanos: f64, max_nanos: f64) -> Vec<String> {
+    match column {
+        Column::Part1 => vec![format!("`{}`", format_part(timing, 1))],
+        Column::Part2 => vec![format!("`{}`", format_part(timing, 2))],
+        Column::Parse => vec![format!(
+            "`{}`",
+            parse_nanos(timing).map_or_else(|| "-".into(), format_nanos)
+        )],
+        Column::Total => vec![format!("`{}`", format_nanos(timing.total_nanos))],
+        Column::Share => {
+            let share = if sum_nanos > 0.0 {
+                timing.total_nanos / sum_nanos * 100.0
+            } else {
+                0.0
+            };
+            vec![format!("`{share:.1}%`")]
+        }
+        Column::Memory => vec![
+            format!("`{}`", format_memory(timing.part_1_memory)),
+            format!("`{}`", format_memory(timing.part_2_memory)),
+        ],
+        Column::Bar => vec![render_bar(timing.total_nanos, max_nanos)],
+    }
+}
+
+fn render_subtotal_cells(column: Column, timings: &Timings, sum_nanos: f64) -> Vec<String> {
+    let sum_part =
+        |part: u8| -> f64 { timings.data.iter().filter_map(|t| t.part_nanos(part)).sum() };
+
+    match column {
+        Column::Part1 => vec![format!("`{}`", format_nanos(sum_part(1)))],
+        Column::Part2 => vec![format!("`{}`", format_nanos(sum_part(2)))],
+        Column::Parse => {
+            let sum: f64 = timings.data.iter().filter_map(parse_nanos).sum();
+            vec![format!("`{}`", format_nanos(sum))]
+        }
+        Column::Total => vec![format!("`{}`", format_nanos(sum_nanos))],
+        Column::Share => vec!["`100.0%`".into()],
+        Column::Memory => vec![String::new(), String::new()],
+        Column::Bar => vec![String::new()],
+    }
+}
+
+fn render_row(cells: &[String]) -> String {
+    format!("| {} |", cells.join(" | "))
+}
+
+fn construct_table(
+    prefix: &str,
+    year: u16,
+    timings: Timings,
+    total_millis: f64,
+    options: &TableOptions,
+    markers: (&str, &str),
+) -> String {
+    let header = format!("{prefix} {year} Benchmarks");
+
+    // memory columns are only rendered by default once memory stats were measured, e.g. via `cargo solve <day> --dhat`.
+    let columns = options.columns.clone().unwrap_or_else(|| {
+        let mut columns = vec![Column::Part1, Column::Part2];
+        if timings.has_memory() {
+            columns.push(Column::Memory);
+        }
+        columns
+    });
+
+    let sum_nanos: f64 = timings.data.iter().map(|t| t.total_nanos).sum();
+    let max_nanos = timings
+        .data
+        .iter()
+        .map(|t| t.total_nanos)
+        .fold(0.0, f64::max);
+
+    let mut slowest: Vec<&Timing> = timings.data.iter().collect();
+    slowest.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    let highlighted: Vec<Day> = slowest
+        .iter()
+        .filter(|t| t.total_nanos > 0.0)
+        .take(options.highlight)
+        .map(|t| t.day)
+        .collect();
+
+    let rows: Vec<&Timing> = if options.sort_by_cost {
+        slowest
+    } else {
+        timings.data.iter().collect()
+    };
+
+    let headers: Vec<String> = std::iter::once("Day")
+        .chain(columns.iter().flat_map(|c| c.headers().iter().copied()))
+        .map(Into::into)
+        .collect();
+
+    let mut lines: Vec<String> = vec![markers.0.into(), header, String::new()];
+    lines.push(render_row(&headers));
+    lines.push(render_row(&vec![":---:".to_string(); headers.len()]));
+
+    for timing in rows {
+        let link = format!(
+            "[Day {}]({})",
             timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            get_path_for_bin(PuzzleId::new(year, timing.day))
+        );
+
+        let mut cells = vec![if highlighted.contains(&timing.day) {
+            format!("**{link}**")
+        } else {
+            link
+        }];
+
+        for column in &columns {
+            cells.extend(render_cells(*column, timing, sum_nanos, max_nanos));
+        }
+
+        lines.push(render_row(&cells));
+    }
+
+    if options.subtotals {
+        let mut cells = vec!["**Total**".to_string()];
+        for column in &columns {
+            cells.extend(render_subtotal_cells(*column, &timings, sum_nanos));
+        }
+        lines.push(render_row(&cells));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(markers.1.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn update_content(
+    s: &mut String,
+    config: &TableConfig,
+    year: u16,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let positions = locate_table(s, config, year)?;
+    let options = parse_marker_options(&positions.start_marker, config)?;
+    let table = construct_table(
+        "##",
+        year,
+        timings,
+        total_millis,
+        &options,
+        (&positions.start_marker, &positions.end_marker),
+    );
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
+/// Update the benchmark table of an event in the readme.
+pub fn update(year: u16, timings: Timings) -> Result<(), Error> {
+    let config = config::get();
+    let path = &config.readme;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(
+        &mut readme,
+        &TableConfig::from_config(config)?,
+        year,
+        timings,
+        total_millis,
+    )?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
-#[cfg(feature = "test_lib")]
+#[cfg(all(test, feature = "test_lib"))]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{update_content, Column, TableConfig};
+    use crate::{
+        day,
+        template::memory::MemoryStats,
+        template::spans::SpanRecord,
+        template::timings::{Timing, Timings},
+    };
+
+    const START: &str = "<!--- benchmarking table year=2024 --->";
+    const MARKER: &str = "<!--- benchmarking table --->";
+
+    fn table_config() -> TableConfig {
+        TableConfig {
+            marker: "benchmarking table".into(),
+            defaults: String::new(),
+            columns: None,
+        }
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -110,18 +502,21 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
                     total_nanos: 9e+10,
+                    ..Default::default()
                 },
             ],
         }
@@ -131,47 +526,114 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &table_config(), 2024, get_mock_timings(), 190.0).unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_markers_are_unbalanced() {
+        let mut s = format!("{} {} {}", START, MARKER, MARKER);
+        update_content(&mut s, &table_config(), 2024, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
-    fn errors_if_too_many_markers_present() {
-        let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+    fn errors_if_year_has_no_table() {
+        let mut s = format!("{}{}", START, MARKER);
+        update_content(&mut s, &table_config(), 2023, get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        let mut s = format!("foo\nbar\n{}{}\nbaz", START, MARKER);
+        update_content(&mut s, &table_config(), 2024, get_mock_timings(), 190.0).unwrap();
+        assert!(s.contains("## 2024 Benchmarks"));
     }
 
     #[test]
     fn updates_existing_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        let mut s = format!("foo\nbar\n{}{}\nbaz", START, MARKER);
+        update_content(&mut s, &table_config(), 2024, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &table_config(), 2024, get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.matches(START).count(), 1);
+        assert_eq!(s.matches(MARKER).count(), 1);
+        assert_eq!(s.matches("## 2024 Benchmarks").count(), 1);
+    }
+
+    #[test]
+    fn updates_the_table_of_the_year() {
+        let start_2023 = "<!--- benchmarking table year=2023 --->";
+        let mut s = format!("{}\n{}\n{}\n{}", start_2023, MARKER, START, MARKER);
+        update_content(&mut s, &table_config(), 2024, get_mock_timings(), 190.0).unwrap();
+        assert!(s.starts_with(&format!(
+            "{}\n{}\n{}\n## 2024 Benchmarks",
+            start_2023, MARKER, START
+        )));
+
+        update_content(&mut s, &table_config(), 2023, get_mock_timings(), 190.0).unwrap();
+        assert!(s.contains("[Day 1](./src/bin/2023_01.rs)"));
+        assert!(s.contains("[Day 1](./src/bin/2024_01.rs)"));
+        assert!(s.find("## 2023 Benchmarks").unwrap() < s.find(START).unwrap());
     }
 
     #[test]
     fn format_benchmarks() {
-        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", START, MARKER);
+        update_content(&mut s, &table_config(), 2024, get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
-            "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "<!--- benchmarking table year=2024 --->",
+            "## 2024 Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/2024_01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2024_02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2024_04.rs) | `40ms` | `50ms` |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_2_memory = Some(MemoryStats {
+            peak_bytes: 2048,
+            total_bytes: 4096,
+            allocations: 3,
+        });
+
+        let mut s = format!("{}\n{}", START, MARKER);
+        update_content(&mut s, &table_config(), 2024, timings, 190.0).unwrap();
+
+        assert!(s.contains("| Day | Part 1 | Part 2 | Part 1 Memory | Part 2 Memory |"));
+        assert!(s.contains("| :---: | :---: | :---: | :---: | :---: |"));
+        assert!(s.contains("| [Day 1](./src/bin/2024_01.rs) | `10ms` | `20ms` | `-` | `2.0 KiB` |"));
+        assert!(s.contains("| [Day 2](./src/bin/2024_02.rs) | `30ms` | `40ms` | `-` | `-` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_options() {
+        let options = "<!--- benchmarking table year=2024 columns=part_1,total,share,bar sort=cost highlight=1 subtotals --->";
+        let mut s = format!("foo\n{}\n{}\nbaz", options, MARKER);
+        update_content(&mut s, &table_config(), 2024, get_mock_timings(), 190.0).unwrap();
+        let expected = [
+            "foo",
+            options,
+            "## 2024 Benchmarks",
+            "",
+            "| Day | Part 1 | Total | Share | Relative |",
+            "| :---: | :---: | :---: | :---: | :---: |",
+            "| **[Day 4](./src/bin/2024_04.rs)** | `40ms` | `90.0s` | `47.4%` | ████████████████ |",
+            "| [Day 2](./src/bin/2024_02.rs) | `30ms` | `70.0s` | `36.8%` | ████████████▌ |",
+            "| [Day 1](./src/bin/2024_01.rs) | `10ms` | `30.0s` | `15.8%` | █████▍ |",
+            "| **Total** | `80.0ms` | `190.0s` | `100.0%` |  |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +642,73 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn keeps_options_when_updating() {
+        let options = "<!--- benchmarking table year=2024 sort=cost --->";
+        let mut s = format!("{}{}", options, MARKER);
+        update_content(&mut s, &table_config(), 2024, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &table_config(), 2024, get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.matches(options).count(), 1);
+        assert!(s.find("[Day 4]").unwrap() < s.find("[Day 1]").unwrap());
+    }
+
+    #[test]
+    fn uses_configured_marker_and_defaults() {
+        let config = TableConfig {
+            marker: "timings".into(),
+            defaults: "columns=total sort=cost".into(),
+            columns: None,
+        };
+        let start = "<!--- timings year=2024 sort=day --->";
+        let mut s = format!("{}\n<!--- timings --->", start);
+        update_content(&mut s, &config, 2024, get_mock_timings(), 190.0).unwrap();
+
+        assert!(s.starts_with(start));
+        assert!(s.ends_with("<!--- timings --->"));
+        assert!(s.contains("| Day | Total |"));
+        assert!(s.find("[Day 1]").unwrap() < s.find("[Day 4]").unwrap());
+    }
+
+    #[test]
+    fn format_benchmarks_with_parse_column() {
+        let span = |name: &str, depth, nanos| SpanRecord {
+            name: name.into(),
+            depth,
+            nanos,
+            calls: 1,
+        };
+
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_spans = vec![span("parse", 0, 1_500.0), span("parse", 1, 700.0)];
+        timings.data[0].part_2_spans = vec![span("parse", 0, 2_000.0), span("walk", 0, 9_000.0)];
+        timings.data[1].part_1_spans = vec![span("walk", 0, 4_000.0)];
+
+        let config = TableConfig {
+            columns: Some(vec![Column::Part1, Column::Parse]),
+            ..table_config()
+        };
+        let mut s = format!("{}\n{}", START, MARKER);
+        update_content(&mut s, &config, 2024, timings, 190.0).unwrap();
+
+        assert!(s.contains("| Day | Part 1 | Parse |"));
+        assert!(s.contains("| [Day 1](./src/bin/2024_01.rs) | `10ms` | `3.5µs` |"));
+        assert!(s.contains("| [Day 2](./src/bin/2024_02.rs) | `30ms` | `-` |"));
+
+        let options = "<!--- benchmarking table year=2024 columns=parse subtotals --->";
+        let mut s = format!("{}\n{}", options, MARKER);
+        update_content(&mut s, &config, 2024, get_mock_timings(), 190.0).unwrap();
+        assert!(s.contains("| Day | Parse |"));
+        assert!(s.contains("| **Total** | `0.0ns` |"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_for_unknown_columns() {
+        let mut s = format!(
+            "<!--- benchmarking table year=2024 columns=foo --->{}",
+            MARKER
+        );
+        update_content(&mut s, &table_config(), 2024, get_mock_timings(), 190.0).unwrap();
+    }
 }
@@ -0,0 +1,407 @@
+/// Module that renders stored benchmark results into standalone reports.
+/// A report combines timings, memory stats and the known answer status of every day.
+use std::{collections::HashMap, fmt::Write, str::FromStr, time::Duration};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    all_days, answers,
+    memory::{format_bytes, MemoryStats},
+    timings::{Timing, Timings},
+    Day, PuzzleId,
+};
+
+/// Width of the bars in the HTML chart, in pixels.
+const CHART_BAR_WIDTH: f64 = 480.0;
+const CHART_ROW_HEIGHT: usize = 24;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Csv,
+    Json,
+    Html,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ReportFormat::Csv),
+            "json" => Ok(ReportFormat::Json),
+            "html" => Ok(ReportFormat::Html),
+            _ => Err(format!("unknown format `{s}`, expecting csv, json or html")),
+        }
+    }
+}
+
+/// Results of a single part.
+#[derive(Clone, Debug, Default)]
+pub struct PartReport {
+    /// Formatted duration, e.g. `1.2ms`.
+    pub time: Option<String>,
+    pub nanos: Option<f64>,
+    pub timed_out: bool,
+    pub memory: Option<MemoryStats>,
+    /// Whether a known answer is stored for the part.
+    pub has_answer: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct DayReport {
+    pub day: Day,
+    pub total_nanos: f64,
+    pub parts: [PartReport; 2],
+}
+
+/// Collect the report rows for every day of the event that has timings or known answers.
+pub fn collect(
+    year: u16,
+    timings: &Timings,
+    has_answer: impl Fn(Day, u8) -> bool,
+) -> Vec<DayReport> {
+    all_days(year)
+        .filter_map(|day| {
+            let timing = timings.data.iter().find(|t| t.day == day);
+            let parts = [1, 2].map(|part| part_report(timing, part, has_answer(day, part)));
+
+            if timing.is_none() && !parts.iter().any(|p| p.has_answer) {
+                return None;
+            }
+
+            Some(DayReport {
+                day,
+                total_nanos: timing.map_or(0.0, |t| t.total_nanos),
+                parts,
+            })
+        })
+        .collect()
+}
+
+/// Collect the report rows of an event from stored timings and known answers.
+pub fn collect_stored(year: u16) -> Vec<DayReport> {
+    collect(year, &Timings::read_from_file(year), |day, part| {
+        answers::read_answer(PuzzleId::new(year, day), None, part).is_some()
+    })
+}
+
+fn part_report(timing: Option<&Timing>, part: u8, has_answer: bool) -> PartReport {
+    let Some(timing) = timing else {
+        return PartReport {
+            has_answer,
+            ..PartReport::default()
+        };
+    };
+
+    let (time, memory) = match part {
+        1 => (timing.part_1.clone(), timing.part_1_memory),
+        _ => (timing.part_2.clone(), timing.part_2_memory),
+    };
+
+    PartReport {
+        time,
+        nanos: timing.part_nanos(part),
+        timed_out: timing.timed_out.contains(&part),
+        memory,
+        has_answer,
+    }
+}
+
+pub fn render(days: &[DayReport], format: ReportFormat) -> String {
+    match format {
+        ReportFormat::Csv => render_csv(days),
+        ReportFormat::Json => render_json(days),
+        ReportFormat::Html => render_html(days),
+    }
+}
+
+fn total_nanos(days: &[DayReport]) -> f64 {
+    days.iter().map(|d| d.total_nanos).sum()
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn render_csv(days: &[DayReport]) -> String {
+    let mut csv = String::from("day,total_nanos");
+
+    for part in [1, 2] {
+        write!(
+            csv,
+            ",part_{part}_time,part_{part}_nanos,part_{part}_timed_out,part_{part}_peak_bytes,\
+            part_{part}_total_bytes,part_{part}_allocations,part_{part}_known_answer"
+        )
+        .unwrap();
+    }
+    csv.push('\n');
+
+    for day in days {
+        write!(csv, "{},{}", day.day, day.total_nanos).unwrap();
+
+        for part in &day.parts {
+            let optional = |value: Option<String>| value.unwrap_or_default();
+            write!(
+                csv,
+                ",{},{},{},{},{},{},{}",
+                optional(part.time.clone()),
+                optional(part.nanos.map(|n| n.to_string())),
+                part.timed_out,
+                optional(part.memory.map(|m| m.peak_bytes.to_string())),
+                optional(part.memory.map(|m| m.total_bytes.to_string())),
+                optional(part.memory.map(|m| m.allocations.to_string())),
+                part.has_answer
+            )
+            .unwrap();
+        }
+        csv.push('\n');
+    }
+
+    csv
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn render_json(days: &[DayReport]) -> String {
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+    map.insert("total_nanos".into(), JsonValue::Number(total_nanos(days)));
+    map.insert(
+        "days".into(),
+        JsonValue::Array(days.iter().map(JsonValue::from).collect()),
+    );
+
+    JsonValue::Object(map).format().unwrap_or_default()
+}
+
+impl From<&DayReport> for JsonValue {
+    fn from(value: &DayReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
+
+        for (i, part) in value.parts.iter().enumerate() {
+            map.insert(format!("part_{}", i + 1), JsonValue::from(part));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&PartReport> for JsonValue {
+    fn from(value: &PartReport) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "time".into(),
+            value
+                .time
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "nanos".into(),
+            value.nanos.map_or(JsonValue::Null, JsonValue::Number),
+        );
+        map.insert("timed_out".into(), JsonValue::Boolean(value.timed_out));
+        map.insert(
+            "memory".into(),
+            value
+                .memory
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert("known_answer".into(), JsonValue::Boolean(value.has_answer));
+
+        JsonValue::Object(map)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn render_chart(days: &[DayReport]) -> String {
+    let max_nanos = days.iter().map(|d| d.total_nanos).fold(0.0, f64::max);
+    let height = days.len() * CHART_ROW_HEIGHT;
+    let label_width = 70.0;
+    let width = label_width + CHART_BAR_WIDTH + 90.0;
+
+    let mut svg = format!(
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" role="img" aria-label="Runtime per day">"#
+    );
+
+    for (i, day) in days.iter().enumerate() {
+        let y = i * CHART_ROW_HEIGHT;
+        let bar_width = if max_nanos > 0.0 {
+            (day.total_nanos / max_nanos * CHART_BAR_WIDTH).max(1.0)
+        } else {
+            0.0
+        };
+
+        write!(
+            svg,
+            r#"<text x="0" y="{}" class="label">Day {}</text><rect x="{label_width}" y="{}" width="{bar_width:.1}" height="{}" class="bar"><title>Day {}: {}</title></rect><text x="{:.1}" y="{}" class="value">{}</text>"#,
+            y + 16,
+            day.day,
+            y + 4,
+            CHART_ROW_HEIGHT - 8,
+            day.day,
+            format_nanos(day.total_nanos),
+            label_width + bar_width + 6.0,
+            y + 16,
+            format_nanos(day.total_nanos),
+        )
+        .unwrap();
+    }
+
+    svg.push_str("</svg>");
+    svg
+}
+
+fn render_html(days: &[DayReport]) -> String {
+    let mut rows = String::new();
+
+    for day in days {
+        write!(rows, "<tr><td>Day {}</td>", day.day).unwrap();
+
+        for part in &day.parts {
+            let time = match (&part.time, part.timed_out) {
+                (Some(time), _) => escape_html(time),
+                (None, true) => "timeout".into(),
+                (None, false) => "-".into(),
+            };
+            let memory = part
+                .memory
+                .map_or_else(|| "-".into(), |m| format_bytes(m.peak_bytes));
+            let answer = if part.has_answer { "✔" } else { "-" };
+
+            write!(rows, "<td>{time}</td><td>{memory}</td><td>{answer}</td>").unwrap();
+        }
+
+        writeln!(rows, "<td>{}</td></tr>", format_nanos(day.total_nanos)).unwrap();
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html lang="en">
+<head>
+<meta charset="utf-8">
+<title>Advent of Code benchmarks</title>
+<style>
+body {{ font-family: system-ui, sans-serif; margin: 2rem; color: #1f2328; }}
+table {{ border-collapse: collapse; margin-bottom: 2rem; }}
+th, td {{ border: 1px solid #d0d7de; padding: 0.3rem 0.8rem; text-align: center; }}
+th {{ background: #f6f8fa; }}
+svg .label, svg .value {{ font-size: 12px; fill: #1f2328; }}
+svg .bar {{ fill: #2da44e; }}
+</style>
+</head>
+<body>
+<h1>Advent of Code benchmarks</h1>
+<p>Total: {total}</p>
+{chart}
+<table>
+<thead>
+<tr><th rowspan="2">Day</th><th colspan="3">Part 1</th><th colspan="3">Part 2</th><th rowspan="2">Total</th></tr>
+<tr><th>Time</th><th>Peak memory</th><th>Known answer</th><th>Time</th><th>Peak memory</th><th>Known answer</th></tr>
+</thead>
+<tbody>
+{rows}</tbody>
+</table>
+</body>
+</html>
+"#,
+        total = format_nanos(total_nanos(days)),
+        chart = render_chart(days),
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{collect, render, DayReport, ReportFormat};
+    use crate::{
+        day,
+        template::{
+            memory::MemoryStats,
+            timings::{Timing, Timings},
+        },
+    };
+
+    fn get_mock_report() -> Vec<DayReport> {
+        let timings = Timings {
+            data: vec![Timing {
+                day: day!(1),
+                part_1: Some("10ms".into()),
+                part_2: None,
+                total_nanos: 1e7,
+                timed_out: vec![2],
+                part_1_memory: Some(MemoryStats {
+                    peak_bytes: 1,
+                    total_bytes: 2,
+                    allocations: 3,
+                }),
+                ..Default::default()
+            }],
+        };
+
+        collect(2024, &timings, |day, part| day == day!(3) && part == 1)
+    }
+
+    #[test]
+    fn collects_days_with_timings_or_answers() {
+        let days = get_mock_report();
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].day, day!(1));
+        assert_eq!(days[0].parts[0].nanos, Some(1e7));
+        assert!(days[0].parts[1].timed_out);
+        assert_eq!(days[1].day, day!(3));
+        assert!(days[1].parts[0].has_answer);
+        assert_eq!(days[1].parts[0].time, None);
+    }
+
+    #[test]
+    fn renders_csv() {
+        let csv = render(&get_mock_report(), ReportFormat::Csv);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("day,total_nanos,part_1_time,part_1_nanos"));
+        assert_eq!(
+            lines[1],
+            "01,10000000,10ms,10000000,false,1,2,3,false,,,true,,,,false"
+        );
+        assert_eq!(lines[2], "03,0,,,false,,,,true,,,false,,,,false");
+    }
+
+    #[test]
+    fn renders_json() {
+        let json = render(&get_mock_report(), ReportFormat::Json);
+        let value: tinyjson::JsonValue = json.parse().unwrap();
+        let days: &Vec<tinyjson::JsonValue> = value["days"].get().unwrap();
+        assert_eq!(days.len(), 2);
+        let memory: &f64 = value["days"][0]["part_1"]["memory"]["allocations"]
+            .get()
+            .unwrap();
+        assert_eq!(*memory, 3.0);
+    }
+
+    #[test]
+    fn renders_html_with_chart() {
+        let html = render(&get_mock_report(), ReportFormat::Html);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<svg"));
+        assert!(html.contains("<title>Day 01: 10.0ms</title>"));
+        assert!(html.contains("<td>Day 01</td><td>10ms</td><td>1 B</td><td>-</td><td>timeout</td>"));
+    }
+}
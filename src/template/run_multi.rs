@@ -1,51 +1,163 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// Options that control how solutions are invoked.
+#[derive(Clone, Copy, Debug)]
+pub struct RunOptions {
+    pub is_release: bool,
+    pub is_timed: bool,
+    /// Number of solutions that may run concurrently. Ignored for timed runs.
+    pub jobs: usize,
+    /// Maximum duration of a single solution part, mirrored to the solution bins.
+    pub timeout: Option<Duration>,
+    /// Build solutions with the counting allocator to report allocations next to timings.
+    pub count_allocs: bool,
+    /// Record timing spans inside solutions, mirrored to the solution bins.
+    pub spans: bool,
+    /// Run solutions against every input of a day instead of the default input only.
+    pub all_inputs: bool,
+}
 
-    let mut need_space = false;
+/// The result of running a set of days.
+#[derive(Debug)]
+pub struct MultiRun {
+    /// Timings of the days that were run, only present for timed runs.
+    pub timings: Option<Timings>,
+    /// Whether any solution failed, e.g. because a part panicked or timed out.
+    pub has_failures: bool,
+}
 
+/// Run the solutions for a set of days of an event and print their output in day order.
+///
+/// When `jobs` is greater than one and the run is not timed, up to `jobs` solutions are run concurrently.
+/// Their output is buffered and printed once all previous days have been printed.
+pub fn run_multi(year: u16, days_to_run: &HashSet<Day>, options: &RunOptions) -> MultiRun {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let puzzles: Vec<PuzzleId> = all_days(year)
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+        .map(|day| PuzzleId::new(year, day))
+        .collect();
+
+    // NOTE: timed runs stay sequential, concurrent solutions would skew each other's benchmarks.
+    if !options.is_timed && options.jobs > 1 {
+        return MultiRun {
+            timings: None,
+            has_failures: run_parallel(&puzzles, options),
+        };
+    }
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    let mut timings: Vec<Timing> = Vec::with_capacity(puzzles.len());
+    let mut has_failures = false;
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    for (i, &puzzle) in puzzles.iter().enumerate() {
+        print_day_header(puzzle.day, i > 0);
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+        let output = child_commands::run_solution(puzzle, options).unwrap();
+        has_failures |= output.failed;
+
+        if output.stdout.is_empty() {
+            println!("Not solved.");
+        } else {
+            let val = child_commands::parse_exec_time(&output.stdout, puzzle.day);
+            timings.push(val);
+        }
+    }
 
-    if is_timed {
+    if options.is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
+        MultiRun {
+            timings: Some(timings),
+            has_failures,
+        }
     } else {
-        None
+        MultiRun {
+            timings: None,
+            has_failures,
+        }
     }
 }
 
+/// Run the solutions on up to `options.jobs` threads. Returns whether any solution failed.
+fn run_parallel(puzzles: &[PuzzleId], options: &RunOptions) -> bool {
+    let next_index = AtomicUsize::new(0);
+    let (tx, rx) = mpsc::channel();
+    let mut has_failures = false;
+
+    thread::scope(|scope| {
+        for _ in 0..options.jobs.min(puzzles.len()) {
+            let tx = tx.clone();
+            let next_index = &next_index;
+
+            scope.spawn(move || loop {
+                let index = next_index.fetch_add(1, Ordering::Relaxed);
+                let Some(&puzzle) = puzzles.get(index) else {
+                    break;
+                };
+
+                let output = child_commands::run_solution_buffered(puzzle, options);
+                if tx.send((index, output)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        // drop the original sender so the receiver is closed once all workers are done.
+        drop(tx);
+
+        // results arrive in completion order, hold them back until all previous days were printed.
+        let mut pending = BTreeMap::new();
+        let mut next_to_print = 0;
+
+        for (index, output) in rx {
+            pending.insert(index, output);
+
+            while let Some(output) = pending.remove(&next_to_print) {
+                print_day_header(puzzles[next_to_print].day, next_to_print > 0);
+
+                let output = output.unwrap();
+                output.stdout.iter().for_each(|line| println!("{line}"));
+                has_failures |= output.failed;
+
+                if output.stdout.is_empty() {
+                    println!("Not solved.");
+                }
+
+                next_to_print += 1;
+            }
+        }
+    });
+
+    has_failures
+}
+
+fn print_day_header(day: Day, need_space: bool) {
+    if need_space {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -59,48 +171,86 @@ impl From<std::io::Error> for Error {
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{Error, RunOptions};
+    use crate::template::{
+        memory::MemoryStats, runner::TIMEOUT_MARKER, spans::SpanRecord,
+        timings::parse_duration_nanos, Day, PuzzleId,
+    };
     use std::{
-        io::{BufRead, BufReader},
-        path::Path,
+        io::{self, BufRead, BufReader, Read},
         process::{Command, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
+    /// Captured output of a solution bin.
+    #[derive(Debug, Default)]
+    pub struct SolutionOutput {
+        /// For buffered runs, stderr is merged into stdout in the order it was written.
+        pub stdout: Vec<String>,
+        /// Whether the solution bin exited with a non-zero status.
+        pub failed: bool,
+    }
+
+    fn build_args(puzzle: PuzzleId, options: &RunOptions) -> Vec<String> {
+        let mut args = vec![
+            "run".to_string(),
+            "--quiet".to_string(),
+            "--bin".to_string(),
+            puzzle.bin_name(),
+        ];
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        if options.is_release {
+            args.push("--release".into());
+        }
 
-        if is_release {
-            args.push("--release");
+        if options.count_allocs {
+            args.push("--features".into());
+            args.push("count-allocs".into());
         }
 
-        if is_timed {
+        args.push("--".into());
+
+        if options.is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+            args.push("--time".into());
+        }
+
+        if options.spans {
+            args.push("--spans".into());
+        }
+
+        if options.all_inputs {
+            args.push("--all-inputs".into());
+        }
+
+        if let Some(timeout) = options.timeout {
+            args.push("--timeout".into());
+            args.push(timeout.as_secs_f64().to_string());
         }
 
+        args
+    }
+
+    /// Run the solution bin for a given puzzle
+    pub fn run_solution(puzzle: PuzzleId, options: &RunOptions) -> Result<SolutionOutput, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !puzzle.bin_path().exists() {
+            return Ok(SolutionOutput::default());
+        }
+
+        run_streamed(&build_args(puzzle, options))
+    }
+
+    /// Run `cargo` with the given arguments, forwarding its output while capturing stdout lines.
+    pub fn run_streamed(args: &[String]) -> Result<SolutionOutput, Error> {
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
         let mut cmd = Command::new("cargo")
-            .args(&args)
+            .args(args)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -123,19 +273,95 @@ pub mod child_commands {
         }
 
         thread.join().unwrap();
-        cmd.wait()?;
+        let status = cmd.wait()?;
+
+        Ok(SolutionOutput {
+            stdout: output,
+            failed: !status.success(),
+        })
+    }
+
+    /// Run the solution bin for a given puzzle without forwarding its output.
+    /// Used when several solutions run concurrently and output needs to be printed in order.
+    /// Like `2>&1`, stdout and stderr share a pipe, so panics show up next to the output they follow.
+    pub fn run_solution_buffered(
+        puzzle: PuzzleId,
+        options: &RunOptions,
+    ) -> Result<SolutionOutput, Error> {
+        // skip command invocation for days that have not been scaffolded yet.
+        if !puzzle.bin_path().exists() {
+            return Ok(SolutionOutput::default());
+        }
+
+        let (mut reader, writer) = io::pipe()?;
+        let mut child = {
+            let mut cmd = Command::new("cargo");
+            cmd.args(build_args(puzzle, options))
+                .stdin(Stdio::null())
+                .stdout(writer.try_clone()?)
+                .stderr(writer);
+            cmd.spawn()?
+            // NOTE: dropping the command closes our ends of the pipe, so reading stops once the child exits.
+        };
 
-        Ok(output)
+        let mut output = Vec::new();
+        reader.read_to_end(&mut output)?;
+        let status = child.wait()?;
+
+        Ok(SolutionOutput {
+            stdout: String::from_utf8_lossy(&output)
+                .lines()
+                .map(ToString::to_string)
+                .collect(),
+            failed: !status.success(),
+        })
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+            ..Default::default()
         };
 
+        output
+            .iter()
+            .filter_map(|l| Some((l.split(':').next()?, MemoryStats::parse_from_line(l)?)))
+            .for_each(|(part, memory)| {
+                if part.contains("Part 1") {
+                    timings.part_1_memory = Some(memory);
+                } else if part.contains("Part 2") {
+                    timings.part_2_memory = Some(memory);
+                }
+            });
+
+        // NOTE: spans are printed below the result line of their part.
+        let mut current_part = None;
+        for line in output {
+            if line.contains("Part 1:") {
+                current_part = Some(1);
+            } else if line.contains("Part 2:") {
+                current_part = Some(2);
+            } else if let Some(span) = SpanRecord::parse_from_line(line) {
+                match current_part {
+                    Some(1) => timings.part_1_spans.push(span),
+                    Some(2) => timings.part_2_spans.push(span),
+                    _ => {}
+                }
+            }
+        }
+
+        output
+            .iter()
+            .filter(|l| l.contains(TIMEOUT_MARKER))
+            .filter_map(|l| l.split(':').next())
+            .for_each(|part| {
+                if part.contains("Part 1") {
+                    timings.timed_out.push(1);
+                } else if part.contains("Part 2") {
+                    timings.timed_out.push(2);
+                }
+            });
+
         output
             .iter()
             .filter_map(|l| {
@@ -164,33 +390,23 @@ pub mod child_commands {
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
     fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
             .trim();
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        let parsed_timing = parse_duration_nanos(str_timing)?;
 
         Some((str_timing, parsed_timing))
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
+    #[cfg(all(test, feature = "test_lib"))]
     macro_rules! assert_approx_eq {
         ($a:expr, $b:expr) => {{
             let (a, b) = (&$a, &$b);
@@ -203,7 +419,7 @@ pub mod child_commands {
         }};
     }
 
-    #[cfg(feature = "test_lib")]
+    #[cfg(all(test, feature = "test_lib"))]
     mod tests {
         use super::parse_exec_time;
 
@@ -250,8 +466,63 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert!(res.part_1.is_none());
+            assert!(res.part_2.is_none());
+        }
+
+        #[test]
+        fn parses_timed_out_parts() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 10 (74.13ms @ 99999 samples)".into(),
+                    "Part 2: ✖ timed out after 5.0s      ".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 74130000_f64);
+            assert_eq!(res.part_1.unwrap(), "74.13ms");
+            assert!(res.part_2.is_none());
+            assert_eq!(res.timed_out, vec![2]);
+        }
+
+        #[test]
+        fn parses_memory_stats() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 10 (74.13ms @ 99999 samples) [mem: 2,048 B peak, 4,096 B total, 3 allocs]".into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_eq!(res.part_1.unwrap(), "74.13ms");
+            assert_eq!(res.part_1_memory.unwrap().peak_bytes, 2048);
+            assert_eq!(res.part_1_memory.unwrap().total_bytes, 4096);
+            assert_eq!(res.part_1_memory.unwrap().allocations, 3);
+            assert_eq!(res.part_2_memory, None);
+        }
+
+        #[test]
+        fn parses_spans() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 10 (1.0ms @ 1000 samples)".into(),
+                    "  ↳ parse: 400.0µs (40.0%)".into(),
+                    "    ↳ lines: 100.0µs (10.0%) ×3".into(),
+                    "Part 2: 10 (2.0ms @ 500 samples)".into(),
+                    "  ↳ walk: 1.5ms (75.0%)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 3000000_f64);
+            assert_eq!(res.part_1_spans.len(), 2);
+            assert_eq!(res.part_1_spans[1].name, "lines");
+            assert_eq!(res.part_1_spans[1].depth, 1);
+            assert_eq!(res.part_1_spans[1].calls, 3);
+            assert_eq!(res.part_2_spans.len(), 1);
+            assert_approx_eq!(res.part_2_spans[0].nanos, 1500000_f64);
         }
     }
 }
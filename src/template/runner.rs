@@ -2,65 +2,262 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
-use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::answers::{self, AnswerCheck};
+use crate::template::aoc_client::{AocClient, AocClientError, Verdict};
+use crate::template::commands::read;
+use crate::template::inputs::{Input, InputKind};
+use crate::template::memory::{self, MemoryStats};
+use crate::template::spans::{self, SpanRecord};
+use crate::template::submissions::{Submission, Submissions};
+use crate::template::{PuzzleId, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{ANSI_BOLD, ANSI_GREEN, ANSI_RED};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Stack size of the thread a solution part runs on, matches the usual main thread stack size.
+const PART_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Printed when a part exceeds its timeout, used to pick up timeouts from the output of solution bins.
+pub const TIMEOUT_MARKER: &str = "timed out after";
+
+/// The outcome of running a single solution part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartStatus {
+    Solved,
+    Unsolved,
+    /// The part returned an answer that differs from the known answer.
+    WrongAnswer,
+    Panicked,
+    TimedOut(Duration),
+    /// The answer could not be submitted.
+    SubmissionFailed,
+}
+
+impl PartStatus {
+    /// Returns `true` if the part did not run to completion.
+    pub fn is_failure(self) -> bool {
+        matches!(
+            self,
+            PartStatus::WrongAnswer
+                | PartStatus::Panicked
+                | PartStatus::TimedOut(_)
+                | PartStatus::SubmissionFailed
+        )
+    }
+}
+
+/// Run a part against an input, `kind` determines the known answer the result is checked against.
+pub fn run_part<I, T>(
+    func: impl Fn(I) -> Option<T> + Send + 'static,
+    input: I,
+    puzzle: PuzzleId,
+    kind: &InputKind,
+    part: u8,
+) -> PartStatus
+where
+    I: Clone + Send + 'static,
+    T: Display + Send + 'static,
+{
     let part_str = format!("Part {part}");
+    let timeout = get_timeout();
+    let is_timed = env::args().any(|x| x == "--time");
+
+    let TimedRun {
+        result,
+        duration,
+        samples,
+        memory,
+        spans,
+    } = match run_on_thread(func, input, &part_str, is_timed, timeout) {
+        Ok(run) => run,
+        Err(RecvTimeoutError::Disconnected) => {
+            print_failure(&part_str, "panicked");
+            return PartStatus::Panicked;
+        }
+        Err(RecvTimeoutError::Timeout) => {
+            let timeout = timeout.unwrap_or_default();
+            print_failure(&part_str, &format!("{TIMEOUT_MARKER} {timeout:.1?}"));
+            return PartStatus::TimedOut(timeout);
+        }
+    };
+
+    let check = result.as_ref().map_or(AnswerCheck::Unknown, |result| {
+        answers::check_answer(puzzle, kind, part, &result.to_string())
+    });
+
+    let memory_str = memory
+        .map(|memory| format!(" {memory}"))
+        .unwrap_or_default();
+    let duration_str = format!(
+        "{}{}{memory_str}",
+        format_duration(&duration, samples),
+        format_check(&check)
+    );
+    print_result(&result, &part_str, &duration_str);
+    spans::print_breakdown(&spans, duration);
+
+    match result {
+        None => PartStatus::Unsolved,
+        Some(_) if matches!(check, AnswerCheck::Incorrect { .. }) => PartStatus::WrongAnswer,
+        Some(result) => {
+            // NOTE: only the default input belongs to the account answers are submitted with.
+            if *kind == InputKind::Default {
+                match submit_result(result, puzzle, part) {
+                    // a rejected answer fails the run, like a wrong known answer.
+                    Some(Ok(verdict)) if verdict.is_wrong() => return PartStatus::WrongAnswer,
+                    Some(Err(_)) => return PartStatus::SubmissionFailed,
+                    _ => {}
+                }
+            }
+            PartStatus::Solved
+        }
+    }
+}
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+/// Run a part on its own thread, so panics and timeouts do not take down the other part.
+/// The timeout applies to the first solve only, benching the part afterwards is not limited by it.
+fn run_on_thread<I, T>(
+    func: impl Fn(I) -> Option<T> + Send + 'static,
+    input: I,
+    part_str: &str,
+    is_timed: bool,
+    timeout: Option<Duration>,
+) -> Result<TimedRun<Option<T>>, RecvTimeoutError>
+where
+    I: Clone + Send + 'static,
+    T: Display + Send + 'static,
+{
+    let (solved_tx, solved_rx) = mpsc::channel();
+    let (tx, rx) = mpsc::channel();
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let hook_part_str = part_str.to_string();
+    thread::Builder::new()
+        .name(part_str.into())
+        .stack_size(PART_STACK_SIZE)
+        .spawn(move || {
+            let run = run_timed(func, input, is_timed, |result| {
+                print_result(result, &hook_part_str, "");
+                let _ = solved_tx.send(());
+            });
+            let _ = tx.send(run);
+        })
+        .unwrap();
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    match timeout {
+        Some(timeout) => solved_rx.recv_timeout(timeout)?,
+        None => solved_rx
+            .recv()
+            .map_err(|_| RecvTimeoutError::Disconnected)?,
     }
+
+    rx.recv().map_err(|_| RecvTimeoutError::Disconnected)
+}
+
+/// Exit after a part timed out. A thread cannot be stopped from the outside, so the timed out part keeps
+/// running until the process exits. Remaining parts are skipped, as the abandoned part would skew their
+/// timings and memory stats.
+pub fn exit_after_timeout() -> ! {
+    println!(
+        "{ANSI_ITALIC}Skipped the remaining parts, the timed out part was abandoned.{ANSI_RESET}"
+    );
+    let _ = stdout().flush();
+    process::exit(1);
+}
+
+/// Print the header of an input when a solution does not run against its default input only.
+pub fn print_input_header(input: &Input, need_space: bool) {
+    if need_space {
+        println!();
+    }
+    println!("{ANSI_ITALIC}{}{ANSI_RESET}", input.label());
+}
+
+/// Parse the `--timeout <seconds>` argument passed to the solution.
+fn get_timeout() -> Option<Duration> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--timeout")? + 1;
+
+    let Some(timeout) = args
+        .get(index)
+        .and_then(|x| x.parse::<f64>().ok())
+        .and_then(|x| Duration::try_from_secs_f64(x).ok())
+    else {
+        eprintln!("Unexpected command-line input. Format: cargo solve 1 --timeout 10");
+        process::exit(1);
+    };
+
+    Some(timeout)
+}
+
+/// Result of a timed solution part.
+struct TimedRun<T> {
+    result: T,
+    duration: Duration,
+    samples: u128,
+    memory: Option<MemoryStats>,
+    spans: Vec<SpanRecord>,
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// Heap usage is measured for the first execution if a memory profiling feature is enabled.
+/// Spans are recorded if `--spans` is passed. When benching, they are recorded in a separate run after the samples,
+/// so recording them doesn't affect the measured duration.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> TimedRun<T> {
+    let record_spans = spans::is_requested();
+
     let timer = Instant::now();
-    let result = {
+    let ((result, memory), first_spans) = spans::record(record_spans && !is_timed, || {
         let input = input.clone();
-
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
-
-        func(input)
-    };
+        memory::measure(|| func(input))
+    });
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
-    };
+    if !is_timed {
+        return TimedRun {
+            result,
+            duration: base_time,
+            samples: 1,
+            memory,
+            spans: first_spans,
+        };
+    }
+
+    let bench_iterations = bench_iterations(&base_time);
+    let duration = bench(&func, input.clone(), bench_iterations);
+    let (_, spans) = spans::record(record_spans, || black_box(func(input)));
 
-    (result, run.0, run.1)
+    TimedRun {
+        result,
+        duration,
+        samples: bench_iterations,
+        memory,
+        spans,
+    }
+}
+
+fn bench_iterations(base_time: &Duration) -> u128 {
+    (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, bench_iterations: u128) -> Duration {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
-
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
@@ -71,11 +268,8 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    #[allow(clippy::cast_possible_truncation)]
+    Duration::from_nanos(average_duration(&timers) as u64)
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -94,6 +288,17 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
+fn format_check(check: &AnswerCheck) -> String {
+    match check {
+        AnswerCheck::Unknown => String::new(),
+        AnswerCheck::Correct => format!(" {ANSI_GREEN}✔{ANSI_RESET}"),
+        AnswerCheck::Incorrect { expected } if !expected.contains('\n') => {
+            format!(" {ANSI_RED}✘{ANSI_RESET} {ANSI_ITALIC}expected {expected}{ANSI_RESET}")
+        }
+        AnswerCheck::Incorrect { .. } => format!(" {ANSI_RED}✘{ANSI_RESET}"),
+    }
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
@@ -129,14 +334,19 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+fn print_failure(part: &str, reason: &str) {
+    print!("\r");
+    println!("{part}: ✖ {ANSI_ITALIC}{reason}{ANSI_RESET}      ");
+}
+
+/// Parse the arguments passed to `solve` and try to submit one part of the solution
+/// if `--submit <part>` was passed for it. Returns the verdict of the server, or the expected verdict
+/// if the submission history shows that the answer is wrong.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<Verdict, AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -159,11 +369,85 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let answer = result.to_string();
+    let mut submissions = Submissions::read_from_file(puzzle.year);
+
+    // answers the server already rejected are not submitted again, to avoid waiting out another timeout.
+    if let Err(rejection) = submissions.check(puzzle.day, part, &answer) {
+        println!("{ANSI_RED}Not submitting result for part {part}, {rejection}{ANSI_RESET}");
+        return Some(Ok(rejection.verdict()));
+    }
+
+    println!("Submitting result for part {part}...");
+    let verdict = AocClient::from_env().submit_answer(puzzle, part, &answer);
+
+    match &verdict {
+        Ok(verdict) => {
+            submissions
+                .data
+                .push(Submission::now(puzzle.day, part, &answer, *verdict));
+            if let Err(e) = submissions.store_file(puzzle.year) {
+                eprintln!("Failed to store submission: {e}");
+            }
+
+            let color = match verdict {
+                Verdict::Correct => ANSI_GREEN,
+                v if v.is_wrong() => ANSI_RED,
+                _ => ANSI_BOLD,
+            };
+            println!("{color}{verdict}{ANSI_RESET}");
+        }
+        Err(e) => eprintln!("Failed to submit answer: {e}"),
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    if let Ok(Verdict::Correct) = verdict {
+        match answers::store_answer(puzzle, part, &answer) {
+            Ok(()) => println!("Stored answer as known answer for part {part}."),
+            Err(e) => eprintln!("Failed to store known answer: {e}"),
+        }
+
+        // solving part one unlocks part two, show its description right away.
+        if part == 1 {
+            println!();
+            if let Err(e) = read::refresh(puzzle) {
+                eprintln!("Failed to refresh puzzle: {e}");
+            }
+        }
+    }
+
+    Some(verdict)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::{sync::mpsc::RecvTimeoutError, thread, time::Duration};
+
+    use super::run_on_thread;
+
+    fn slow_part(millis: u64) -> impl Fn(()) -> Option<u64> + Send + 'static {
+        move |()| {
+            thread::sleep(Duration::from_millis(millis));
+            Some(millis)
+        }
+    }
+
+    #[test]
+    fn times_out_slow_parts() {
+        let timeout = Some(Duration::from_millis(50));
+        let run = run_on_thread(slow_part(500), (), "Part 1", false, timeout);
+        assert!(matches!(run, Err(RecvTimeoutError::Timeout)));
+    }
+
+    #[test]
+    fn applies_timeout_to_the_first_solve_only() {
+        // the part solves within the timeout, but benching it takes at least ten samples.
+        let timeout = Some(Duration::from_millis(200));
+        let run = run_on_thread(slow_part(30), (), "Part 1", true, timeout).unwrap();
+
+        assert_eq!(run.result, Some(30));
+        assert!(run.samples >= 10);
+        assert!(run.duration >= Duration::from_millis(30));
+    }
 }
@@ -0,0 +1,332 @@
+/// Module that records nested timing spans inside solutions.
+///
+/// Spans are opened with the [`span!`](crate::span) macro and recorded into a thread-local tree
+/// while a part runs with `--spans`. Without the flag, opening a span is a cheap no-op.
+/// Spans opened on other threads than the one running the part are not recorded.
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    env,
+    time::{Duration, Instant},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::timings::parse_duration_nanos;
+
+const SPAN_PREFIX: &str = "↳ ";
+
+/// Open a timing span that is closed when the returned guard is dropped.
+///
+/// ```ignore
+/// let _walk = span!("walk");
+/// // or time a single expression:
+/// let grid = span!("parse", Grid::from_input(input));
+/// ```
+#[macro_export]
+macro_rules! span {
+    ($name:expr) => {
+        $crate::template::spans::enter($name)
+    };
+    ($name:expr, $body:expr) => {{
+        let _span = $crate::template::spans::enter($name);
+        $body
+    }};
+}
+
+/// A recorded span, flattened from the span tree in depth-first order.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpanRecord {
+    pub name: String,
+    /// Nesting level, top-level spans of a part have depth `0`.
+    pub depth: usize,
+    /// Time spent in the span per run of the part.
+    pub nanos: f64,
+    /// Number of times the span was entered per run of the part.
+    pub calls: u64,
+}
+
+struct Node {
+    name: &'static str,
+    duration: Duration,
+    calls: u64,
+    children: Vec<usize>,
+}
+
+/// Tree of spans, the first node is the implicit root.
+struct Recorder {
+    nodes: Vec<Node>,
+    stack: Vec<usize>,
+}
+
+thread_local! {
+    static RECORDER: RefCell<Option<Recorder>> = const { RefCell::new(None) };
+}
+
+/// Closes its span on drop.
+#[must_use = "the span is closed as soon as the guard is dropped"]
+pub struct SpanGuard {
+    /// Recorded node and start time, `None` if spans are not recorded.
+    active: Option<(usize, Instant)>,
+}
+
+/// Open a span on the current thread, prefer the [`span!`](crate::span) macro.
+/// Spans with the same name and parent are merged into one node.
+pub fn enter(name: &'static str) -> SpanGuard {
+    let node = RECORDER.with(|recorder| {
+        let mut recorder = recorder.borrow_mut();
+        let recorder = recorder.as_mut()?;
+        let parent = *recorder.stack.last()?;
+
+        let existing = recorder.nodes[parent]
+            .children
+            .iter()
+            .copied()
+            .find(|&child| recorder.nodes[child].name == name);
+
+        let node = existing.unwrap_or_else(|| {
+            recorder.nodes.push(Node {
+                name,
+                duration: Duration::ZERO,
+                calls: 0,
+                children: vec![],
+            });
+            let node = recorder.nodes.len() - 1;
+            recorder.nodes[parent].children.push(node);
+            node
+        });
+
+        recorder.nodes[node].calls += 1;
+        recorder.stack.push(node);
+        Some(node)
+    });
+
+    SpanGuard {
+        active: node.map(|node| (node, Instant::now())),
+    }
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        let Some((node, start)) = self.active else {
+            return;
+        };
+
+        let elapsed = start.elapsed();
+
+        RECORDER.with(|recorder| {
+            if let Some(recorder) = recorder.borrow_mut().as_mut() {
+                recorder.nodes[node].duration += elapsed;
+
+                if let Some(index) = recorder.stack.iter().rposition(|&n| n == node) {
+                    recorder.stack.truncate(index);
+                }
+            }
+        });
+    }
+}
+
+/// Returns `true` if `--spans` was passed to the solution.
+pub fn is_requested() -> bool {
+    env::args().any(|x| x == "--spans")
+}
+
+/// Run `func` and record the spans opened by it on the current thread.
+/// `func` should run the part once, spans are recorded in a separate run when benching.
+pub fn record<T>(enabled: bool, func: impl FnOnce() -> T) -> (T, Vec<SpanRecord>) {
+    if !enabled {
+        return (func(), vec![]);
+    }
+
+    RECORDER.with(|recorder| {
+        *recorder.borrow_mut() = Some(Recorder {
+            nodes: vec![Node {
+                name: "",
+                duration: Duration::ZERO,
+                calls: 0,
+                children: vec![],
+            }],
+            stack: vec![0],
+        });
+    });
+
+    let result = func();
+
+    let recorder = RECORDER.with(|recorder| recorder.borrow_mut().take());
+    let mut spans = vec![];
+
+    if let Some(recorder) = recorder {
+        flatten(&recorder, 0, 0, &mut spans);
+    }
+
+    (result, spans)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn flatten(recorder: &Recorder, node: usize, depth: usize, spans: &mut Vec<SpanRecord>) {
+    for &child in &recorder.nodes[node].children {
+        let child_node = &recorder.nodes[child];
+
+        spans.push(SpanRecord {
+            name: child_node.name.to_string(),
+            depth,
+            nanos: child_node.duration.as_nanos() as f64,
+            calls: child_node.calls,
+        });
+
+        flatten(recorder, child, depth + 1, spans);
+    }
+}
+
+/// Print the spans of a part as an indented breakdown below its result.
+pub fn print_breakdown(spans: &[SpanRecord], part_duration: Duration) {
+    for span in spans {
+        println!("{}", span.to_line(part_duration));
+    }
+}
+
+impl SpanRecord {
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn to_line(&self, part_duration: Duration) -> String {
+        let share = if part_duration.is_zero() {
+            0.0
+        } else {
+            self.nanos / part_duration.as_nanos() as f64 * 100.0
+        };
+
+        format!(
+            "{}{SPAN_PREFIX}{}: {:.1?} ({share:.1}%){}",
+            "  ".repeat(self.depth + 1),
+            self.name,
+            Duration::from_nanos(self.nanos as u64),
+            if self.calls > 1 {
+                format!(" ×{}", self.calls)
+            } else {
+                String::new()
+            }
+        )
+    }
+
+    /// Parse a span printed by [`print_breakdown`].
+    pub fn parse_from_line(line: &str) -> Option<Self> {
+        let trimmed = line.trim_start_matches(' ');
+        let indent = line.len() - trimmed.len();
+        let rest = trimmed.strip_prefix(SPAN_PREFIX)?;
+
+        let (rest, calls) = match rest.rsplit_once(" ×") {
+            Some((rest, calls)) => (rest, calls.parse().ok()?),
+            None => (rest, 1),
+        };
+
+        let (rest, _share) = rest.rsplit_once(" (")?;
+        let (name, duration) = rest.rsplit_once(": ")?;
+
+        Some(SpanRecord {
+            name: name.to_string(),
+            depth: (indent / 2).checked_sub(1)?,
+            nanos: parse_duration_nanos(duration)?,
+            calls,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&SpanRecord> for JsonValue {
+    fn from(value: &SpanRecord) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("name".into(), JsonValue::String(value.name.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("depth".into(), JsonValue::Number(value.depth as f64));
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("calls".into(), JsonValue::Number(value.calls as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for SpanRecord {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected span to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected span.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(SpanRecord {
+            name: json
+                .get("name")
+                .and_then(|v| v.get::<String>())
+                .ok_or("Expected span.name to be a string.")?
+                .clone(),
+            depth: number("depth")? as usize,
+            nanos: number("nanos")?,
+            calls: number("calls")? as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::time::Duration;
+
+    use super::{enter, record, SpanRecord};
+
+    #[test]
+    fn records_nested_spans() {
+        let (result, spans) = record(true, || {
+            let _parse = enter("parse");
+            for _ in 0..3 {
+                let _walk = enter("walk");
+                drop(enter("step"));
+            }
+            42
+        });
+
+        assert_eq!(result, 42);
+        let names: Vec<(&str, usize, u64)> = spans
+            .iter()
+            .map(|s| (s.name.as_str(), s.depth, s.calls))
+            .collect();
+        assert_eq!(names, vec![("parse", 0, 1), ("walk", 1, 3), ("step", 2, 3)]);
+    }
+
+    #[test]
+    fn ignores_spans_when_disabled() {
+        let (result, spans) = record(false, || crate::span!("parse", 1 + 1));
+        assert_eq!(result, 2);
+        assert!(spans.is_empty());
+        // spans opened outside of a recording are no-ops.
+        drop(enter("outside"));
+    }
+
+    #[test]
+    fn prints_and_parses_lines() {
+        let span = SpanRecord {
+            name: "walk: loop".into(),
+            depth: 1,
+            nanos: 250_000.0,
+            calls: 4,
+        };
+
+        let line = span.to_line(Duration::from_millis(1));
+        assert_eq!(line, "    ↳ walk: loop: 250.0µs (25.0%) ×4");
+        assert_eq!(SpanRecord::parse_from_line(&line), Some(span));
+        assert_eq!(SpanRecord::parse_from_line("Part 1: 42 (1.0ms)"), None);
+    }
+}
@@ -0,0 +1,207 @@
+/// Module that collects the progress of every day into an advent calendar overview.
+/// The status of a day is derived from the files of its event: the solution bin, input, examples,
+/// known answers and stored timings. Tests are only run on request, as that builds every bin.
+use std::{fs, path::Path};
+
+use crate::template::{
+    all_days, answers, timings::Timings, Day, PuzzleId, ANSI_BOLD, ANSI_GREEN, ANSI_ITALIC,
+    ANSI_RED, ANSI_RESET,
+};
+
+const COLUMNS: usize = 5;
+
+/// Progress of a single day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DayStatus {
+    pub day: Day,
+    pub has_bin: bool,
+    pub has_input: bool,
+    pub has_example: bool,
+    /// `None` if tests were not run.
+    pub tests_passed: Option<bool>,
+    /// Parts with a known answer for the default input, which equals a star.
+    pub stars: u8,
+    /// Total runtime of both parts in nanoseconds, if benchmarked.
+    pub benchmark_nanos: Option<f64>,
+}
+
+/// Collect the status of the days of an event from the files in the repository.
+/// `run_tests` is called for days with a solution bin and returns whether its tests pass.
+pub fn collect(
+    year: u16,
+    timings: &Timings,
+    run_tests: Option<&dyn Fn(PuzzleId) -> bool>,
+) -> Vec<DayStatus> {
+    all_days(year)
+        .map(|day| {
+            let puzzle = PuzzleId::new(year, day);
+            let data_dir = puzzle.data_dir();
+            let has_bin = puzzle.bin_path().is_file();
+
+            DayStatus {
+                day,
+                has_bin,
+                has_input: is_non_empty(&data_dir.join(format!("inputs/{day}.txt"))),
+                has_example: is_non_empty(&data_dir.join(format!("examples/{day}.txt")))
+                    || is_non_empty(&data_dir.join(format!("examples/{day}-1.txt")))
+                    || is_non_empty(&data_dir.join(format!("examples/{day}-2.txt"))),
+                tests_passed: run_tests.filter(|_| has_bin).map(|run| run(puzzle)),
+                stars: [1, 2]
+                    .into_iter()
+                    .filter(|part| answers::read_answer(puzzle, None, *part).is_some())
+                    .count()
+                    .try_into()
+                    .unwrap_or_default(),
+                benchmark_nanos: timings
+                    .data
+                    .iter()
+                    .find(|t| t.day == day && (t.part_1.is_some() || t.part_2.is_some()))
+                    .map(|t| t.total_nanos),
+            }
+        })
+        .collect()
+}
+
+fn is_non_empty(path: &Path) -> bool {
+    fs::read_to_string(path).is_ok_and(|contents| !contents.trim().is_empty())
+}
+
+impl DayStatus {
+    /// Flags of the day, `·` marks what is missing.
+    fn flags(&self) -> String {
+        let flag = |present: bool, flag: char| {
+            if present {
+                flag.to_string()
+            } else {
+                "·".into()
+            }
+        };
+
+        let tests = match self.tests_passed {
+            Some(true) => format!("{ANSI_GREEN}T{ANSI_RESET}"),
+            Some(false) => format!("{ANSI_RED}T{ANSI_RESET}"),
+            None => "·".into(),
+        };
+
+        [
+            flag(self.has_bin, 'S'),
+            flag(self.has_input, 'I'),
+            flag(self.has_example, 'E'),
+            tests,
+            flag(self.benchmark_nanos.is_some(), 'B'),
+        ]
+        .concat()
+    }
+
+    fn cell(&self) -> String {
+        let day = self.day;
+        let stars = format!(
+            "{}{}",
+            "★".repeat(self.stars.into()),
+            "☆".repeat((2 - self.stars).into())
+        );
+        format!("{ANSI_BOLD}{day}{ANSI_RESET} {stars} {}", self.flags())
+    }
+}
+
+/// Render the days as a calendar grid, followed by a legend and totals.
+#[must_use]
+pub fn render(days: &[DayStatus]) -> String {
+    let mut lines: Vec<String> = days
+        .chunks(COLUMNS)
+        .map(|row| {
+            row.iter()
+                .map(DayStatus::cell)
+                .collect::<Vec<_>>()
+                .join("   ")
+        })
+        .collect();
+
+    let count = |f: fn(&DayStatus) -> bool| days.iter().filter(|d| f(d)).count();
+    let stars: usize = days.iter().map(|d| usize::from(d.stars)).sum();
+    let tested = count(|d| d.tests_passed.is_some());
+
+    lines.push(String::new());
+    lines.push(format!(
+        "{ANSI_ITALIC}S solution  I input  E example  T tests  B benchmark  ★ known answer{ANSI_RESET}"
+    ));
+    lines.push(String::new());
+    lines.push(format!(
+        "{ANSI_BOLD}Stars:{ANSI_RESET} {stars}/{}",
+        days.len() * 2
+    ));
+    lines.push(format!(
+        "{ANSI_BOLD}Solutions:{ANSI_RESET} {}  {ANSI_BOLD}Inputs:{ANSI_RESET} {}  {ANSI_BOLD}Examples:{ANSI_RESET} {}",
+        count(|d| d.has_bin),
+        count(|d| d.has_input),
+        count(|d| d.has_example)
+    ));
+
+    if tested > 0 {
+        lines.push(format!(
+            "{ANSI_BOLD}Tests:{ANSI_RESET} {}/{tested} passing",
+            count(|d| d.tests_passed == Some(true))
+        ));
+    }
+
+    let benchmarked: Vec<f64> = days.iter().filter_map(|d| d.benchmark_nanos).collect();
+    if benchmarked.is_empty() {
+        lines.push(format!("{ANSI_BOLD}Benchmarks:{ANSI_RESET} 0"));
+    } else {
+        lines.push(format!(
+            "{ANSI_BOLD}Benchmarks:{ANSI_RESET} {}  {ANSI_BOLD}Total runtime:{ANSI_RESET} {:.2}ms",
+            benchmarked.len(),
+            benchmarked.iter().sum::<f64>() / 1_000_000_f64
+        ));
+    }
+
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{render, DayStatus};
+    use crate::template::all_days;
+
+    #[test]
+    fn renders_calendar() {
+        let mut days: Vec<DayStatus> = all_days(2024)
+            .map(|day| DayStatus {
+                day,
+                has_bin: false,
+                has_input: false,
+                has_example: false,
+                tests_passed: None,
+                stars: 0,
+                benchmark_nanos: None,
+            })
+            .collect();
+
+        days[0] = DayStatus {
+            has_bin: true,
+            has_input: true,
+            has_example: true,
+            tests_passed: Some(true),
+            stars: 2,
+            benchmark_nanos: Some(1_500_000.0),
+            ..days[0].clone()
+        };
+        days[1] = DayStatus {
+            has_bin: true,
+            stars: 1,
+            ..days[1].clone()
+        };
+
+        let rendered = render(&days);
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(lines.len(), 5 + 7);
+        assert!(lines[0].contains("01\x1b[0m ★★ SIE\x1b[32mT\x1b[0mB"));
+        assert!(lines[0].contains("02\x1b[0m ★☆ S····"));
+        assert!(rendered.contains("Stars:\x1b[0m 3/50"));
+        assert!(rendered.contains("Tests:\x1b[0m 1/1 passing"));
+        assert!(rendered.contains("Total runtime:\x1b[0m 1.50ms"));
+    }
+}
@@ -0,0 +1,351 @@
+/// Module that keeps a history of submitted answers in `data/{year}/submissions.json`.
+/// The history is used to refuse answers the server already rejected, including answers
+/// outside the bounds learned from "too high" and "too low" verdicts.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::Error,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{aoc_client::Verdict, data_dir, Day};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+
+/// A submitted answer and the verdict of the server.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+impl Submission {
+    #[must_use]
+    pub fn now(day: Day, part: u8, answer: &str, verdict: Verdict) -> Self {
+        Submission {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+        }
+    }
+}
+
+/// Why an answer is not submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    /// The answer was submitted before and rejected.
+    KnownWrong(Verdict),
+    /// The answer is not lower than an answer that was too high.
+    TooHigh(String),
+    /// The answer is not higher than an answer that was too low.
+    TooLow(String),
+}
+
+impl Rejection {
+    /// The verdict the server would return for the answer.
+    #[must_use]
+    pub fn verdict(&self) -> Verdict {
+        match self {
+            Rejection::KnownWrong(verdict) => *verdict,
+            Rejection::TooHigh(_) => Verdict::TooHigh,
+            Rejection::TooLow(_) => Verdict::TooLow,
+        }
+    }
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::KnownWrong(verdict) => {
+                write!(f, "the answer was already submitted: {verdict}")
+            }
+            Rejection::TooHigh(bound) => {
+                write!(f, "the answer is too high, {bound} was already too high.")
+            }
+            Rejection::TooLow(bound) => {
+                write!(f, "the answer is too low, {bound} was already too low.")
+            }
+        }
+    }
+}
+
+/// All submissions, in the order they were made.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate the submissions of an event to a JSON file.
+    pub fn store_file(&self, year: u16) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        fs::create_dir_all(data_dir(year))?;
+        let mut file = fs::File::create(data_dir(year).join(SUBMISSIONS_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the submissions of an event from a JSON file. If not present, returns empty submissions.
+    #[must_use]
+    pub fn read_from_file(year: u16) -> Self {
+        fs::read_to_string(data_dir(year).join(SUBMISSIONS_FILE_NAME))
+            .map_err(|x| x.to_string())
+            .and_then(Submissions::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Check an answer against the earlier submissions of a part.
+    /// Bounds only apply if the answer and the bounds are integers.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Result<(), Rejection> {
+        let submissions = self
+            .data
+            .iter()
+            .filter(|s| s.day == day && s.part == part && s.verdict.is_wrong());
+
+        if let Some(s) = submissions.clone().find(|s| s.answer == answer) {
+            return Err(Rejection::KnownWrong(s.verdict));
+        }
+
+        let Ok(value) = answer.trim().parse::<i128>() else {
+            return Ok(());
+        };
+
+        let bound = |verdict: Verdict| {
+            submissions
+                .clone()
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| Some((s.answer.trim().parse::<i128>().ok()?, &s.answer)))
+        };
+
+        if let Some((_, high)) = bound(Verdict::TooHigh)
+            .filter(|(high, _)| value >= *high)
+            .min()
+        {
+            return Err(Rejection::TooHigh(high.clone()));
+        }
+
+        if let Some((_, low)) = bound(Verdict::TooLow)
+            .filter(|(low, _)| value <= *low)
+            .max()
+        {
+            return Err(Rejection::TooLow(low.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn verdict_to_str(verdict: Verdict) -> &'static str {
+    match verdict {
+        Verdict::Correct => "correct",
+        Verdict::Wrong => "wrong",
+        Verdict::TooHigh => "too_high",
+        Verdict::TooLow => "too_low",
+        Verdict::AlreadySolved => "already_solved",
+        Verdict::RateLimited(_) => "rate_limited",
+    }
+}
+
+fn verdict_from_str(s: &str) -> Option<Verdict> {
+    match s {
+        "correct" => Some(Verdict::Correct),
+        "wrong" => Some(Verdict::Wrong),
+        "too_high" => Some(Verdict::TooHigh),
+        "too_low" => Some(Verdict::TooLow),
+        "already_solved" => Some(Verdict::AlreadySolved),
+        // NOTE: the time left to wait is only meaningful at the time of the submission.
+        "rate_limited" => Some(Verdict::RateLimited(None)),
+        _ => None,
+    }
+}
+
+impl From<&Submissions> for JsonValue {
+    fn from(value: &Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        Submissions::try_from(&json)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submissions {
+    type Error = String;
+
+    fn try_from(json: &JsonValue) -> Result<Self, Self::Error> {
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(verdict_to_str(value.verdict).into()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .ok_or(format!("Expected submission.{key} to be a string."))
+        };
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected submission.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Submission {
+            day: Day::from_str(string("day")?)
+                .map_err(|_| "Expected submission.day to be a Day struct.")?,
+            part: number("part")? as u8,
+            answer: string("answer")?.clone(),
+            verdict: verdict_from_str(string("verdict")?)
+                .ok_or("Expected submission.verdict to be a known verdict.")?,
+            timestamp: number("timestamp")? as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::str::FromStr;
+
+    use tinyjson::JsonValue;
+
+    use super::{Rejection, Submission, Submissions};
+    use crate::{day, template::aoc_client::Verdict};
+
+    fn submission(part: u8, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            day: day!(6),
+            part,
+            answer: answer.into(),
+            verdict,
+            timestamp: 1_733_461_200,
+        }
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let submissions = Submissions {
+            data: vec![
+                submission(1, "abc", Verdict::Wrong),
+                submission(1, "def", Verdict::RateLimited(None)),
+            ],
+        };
+
+        assert_eq!(
+            submissions.check(day!(6), 1, "abc"),
+            Err(Rejection::KnownWrong(Verdict::Wrong))
+        );
+        assert_eq!(submissions.check(day!(6), 1, "def"), Ok(()));
+        assert_eq!(submissions.check(day!(6), 2, "abc"), Ok(()));
+        assert_eq!(submissions.check(day!(7), 1, "abc"), Ok(()));
+    }
+
+    #[test]
+    fn enforces_bounds() {
+        let submissions = Submissions {
+            data: vec![
+                submission(1, "500", Verdict::TooHigh),
+                submission(1, "400", Verdict::TooHigh),
+                submission(1, "100", Verdict::TooLow),
+            ],
+        };
+
+        assert_eq!(
+            submissions.check(day!(6), 1, "450"),
+            Err(Rejection::TooHigh("400".into()))
+        );
+        assert_eq!(
+            submissions.check(day!(6), 1, "100"),
+            Err(Rejection::KnownWrong(Verdict::TooLow))
+        );
+        assert_eq!(
+            submissions.check(day!(6), 1, "-3"),
+            Err(Rejection::TooLow("100".into()))
+        );
+        assert_eq!(submissions.check(day!(6), 1, "399"), Ok(()));
+        assert_eq!(submissions.check(day!(6), 1, "x"), Ok(()));
+    }
+
+    #[test]
+    fn handles_json_submissions() {
+        let submissions = Submissions {
+            data: vec![
+                submission(1, "41", Verdict::TooLow),
+                submission(1, "42", Verdict::Correct),
+            ],
+        };
+
+        let json = JsonValue::from(&submissions).stringify().unwrap();
+        let parsed = Submissions::try_from(&JsonValue::from_str(&json).unwrap()).unwrap();
+        assert_eq!(parsed.data, submissions.data);
+    }
+}
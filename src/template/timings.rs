@@ -1,9 +1,9 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{data_dir, memory::MemoryStats, spans::SpanRecord, Day};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -12,6 +12,30 @@ pub struct Timing {
     pub part_1: Option<String>,
     pub part_2: Option<String>,
     pub total_nanos: f64,
+    /// Parts that were aborted because they exceeded the timeout.
+    pub timed_out: Vec<u8>,
+    pub part_1_memory: Option<MemoryStats>,
+    pub part_2_memory: Option<MemoryStats>,
+    /// Spans recorded with `--spans`, in depth-first order.
+    pub part_1_spans: Vec<SpanRecord>,
+    pub part_2_spans: Vec<SpanRecord>,
+}
+
+/// An empty timing of the first day, fill in the fields that matter with struct update syntax.
+impl Default for Timing {
+    fn default() -> Self {
+        Timing {
+            day: crate::day!(1),
+            part_1: None,
+            part_2: None,
+            total_nanos: 0.0,
+            timed_out: vec![],
+            part_1_memory: None,
+            part_2_memory: None,
+            part_1_spans: vec![],
+            part_2_spans: vec![],
+        }
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -22,27 +46,41 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate the timings of an event to a JSON file.
+    pub fn store_file(&self, year: u16) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        fs::create_dir_all(data_dir(year))?;
+        let mut file = fs::File::create(data_dir(year).join(TIMINGS_FILE_NAME))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate the timings of an event from a JSON file. If not present, returns empty timings.
+    pub fn read_from_file(year: u16) -> Self {
+        fs::read_to_string(data_dir(year).join(TIMINGS_FILE_NAME))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Memory stats and spans are measured separately from timings, so they are kept if `other` has none.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+
+            if let Some(stored) = self.data.iter().find(|t| t.day == timing.day) {
+                timing.part_1_memory = timing.part_1_memory.or(stored.part_1_memory);
+                timing.part_2_memory = timing.part_2_memory.or(stored.part_2_memory);
+
+                if timing.part_1_spans.is_empty() && timing.part_2_spans.is_empty() {
+                    timing.part_1_spans.clone_from(&stored.part_1_spans);
+                    timing.part_2_spans.clone_from(&stored.part_2_spans);
+                }
+            }
+
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -51,7 +89,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -60,6 +98,13 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Returns `true` if memory stats are stored for any part.
+    pub fn has_memory(&self) -> bool {
+        self.data
+            .iter()
+            .any(|t| t.part_1_memory.is_some() || t.part_2_memory.is_some())
+    }
+
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
@@ -67,6 +112,32 @@ impl Timings {
     }
 }
 
+impl Timing {
+    /// Duration of a part in nanoseconds, if the part was benched.
+    pub fn part_nanos(&self, part: u8) -> Option<f64> {
+        match part {
+            1 => self.part_1.as_deref().and_then(parse_duration_nanos),
+            2 => self.part_2.as_deref().and_then(parse_duration_nanos),
+            _ => None,
+        }
+    }
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
+/// Parse a duration formatted with `{:?}` (e.g. `74.13ms`) into nanoseconds.
+pub fn parse_duration_nanos(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s {
+        s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
@@ -87,7 +158,14 @@ impl TryFrom<String> for Timings {
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        Timings::try_from(&json)
+    }
+}
+
+impl TryFrom<&JsonValue> for Timings {
+    type Error = String;
 
+    fn try_from(json: &JsonValue) -> Result<Self, Self::Error> {
         let json_data = json
             .get::<HashMap<String, JsonValue>>()
             .ok_or("expected JSON document to be an object.")?
@@ -133,6 +211,39 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "timed_out".into(),
+            JsonValue::Array(
+                value
+                    .timed_out
+                    .iter()
+                    .map(|part| JsonValue::Number(f64::from(*part)))
+                    .collect(),
+            ),
+        );
+
+        for (key, memory) in [
+            ("part_1_memory", &value.part_1_memory),
+            ("part_2_memory", &value.part_2_memory),
+        ] {
+            map.insert(
+                key.into(),
+                memory.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
+
+        for (key, spans) in [
+            ("part_1_spans", &value.part_1_spans),
+            ("part_2_spans", &value.part_2_spans),
+        ] {
+            if !spans.is_empty() {
+                map.insert(
+                    key.into(),
+                    JsonValue::Array(spans.iter().map(JsonValue::from).collect()),
+                );
+            }
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -166,18 +277,55 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: timings stored before timeouts were tracked do not have this key.
+        let timed_out = match json.get("timed_out") {
+            None => vec![],
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("Expected timing.timed_out to be an array.")?
+                .iter()
+                .map(|part| match part.get::<f64>() {
+                    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                    Some(part) => Ok(*part as u8),
+                    None => Err("Expected timing.timed_out to contain part numbers."),
+                })
+                .collect::<Result<_, _>>()?,
+        };
+
+        // NOTE: memory stats are only present if they were measured.
+        let parse_memory = |key: &str| match json.get(key) {
+            Some(v) if !v.is_null() => MemoryStats::try_from(v).map(Some),
+            _ => Ok(None),
+        };
+
+        // NOTE: spans are only present if they were recorded.
+        let parse_spans = |key: &str| match json.get(key) {
+            Some(v) => v
+                .get::<Vec<JsonValue>>()
+                .ok_or(format!("Expected timing.{key} to be an array."))?
+                .iter()
+                .map(SpanRecord::try_from)
+                .collect::<Result<Vec<_>, _>>(),
+            None => Ok(vec![]),
+        };
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
             total_nanos,
+            timed_out,
+            part_1_memory: parse_memory("part_1_memory")?,
+            part_2_memory: parse_memory("part_2_memory")?,
+            part_1_spans: parse_spans("part_1_spans")?,
+            part_2_spans: parse_spans("part_2_spans")?,
         })
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-#[cfg(feature = "test_lib")]
+#[cfg(all(test, feature = "test_lib"))]
 mod tests {
     use crate::day;
 
@@ -191,18 +339,21 @@ mod tests {
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
                     total_nanos: 3e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
                     total_nanos: 7e+10,
+                    ..Default::default()
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
                     total_nanos: 4e+10,
+                    ..Default::default()
                 },
             ],
         }
@@ -221,6 +372,36 @@ mod tests {
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.timed_out, Vec::<u8>::new());
+        }
+
+        #[test]
+        fn handles_timed_out_parts() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "timed_out": [2] }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.timed_out, vec![2]);
+        }
+
+        #[test]
+        fn handles_memory_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_memory": { "peak_bytes": 10, "total_bytes": 20, "allocations": 2 }, "part_2_memory": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_memory.unwrap().total_bytes, 20);
+            assert_eq!(timing.part_2_memory, None);
+        }
+
+        #[test]
+        fn handles_spans() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_spans": [{ "name": "parse", "depth": 0, "nanos": 500, "calls": 1 }, { "name": "line", "depth": 1, "nanos": 100, "calls": 5 }] }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_spans.len(), 2);
+            assert_eq!(timing.part_1_spans[1].name, "line");
+            assert_eq!(timing.part_1_spans[1].calls, 5);
+            assert!(timing.part_2_spans.is_empty());
         }
 
         #[test]
@@ -253,7 +434,7 @@ mod tests {
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
-            let value = JsonValue::try_from(timings).unwrap();
+            let value = JsonValue::from(timings);
             assert_eq!(
                 value
                     .get::<HashMap<String, JsonValue>>()
@@ -282,10 +463,11 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
                     total_nanos: 3_000_000_000_f64,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert!(timings.is_day_complete(day!(1)));
         }
 
         #[test]
@@ -296,10 +478,11 @@ mod tests {
                     part_1: Some("1ms".into()),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert!(!timings.is_day_complete(day!(1)));
         }
 
         #[test]
@@ -310,17 +493,21 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    ..Default::default()
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert!(!timings.is_day_complete(day!(1)));
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                memory::MemoryStats,
+                timings::{Timing, Timings},
+            },
         };
 
         use super::get_mock_timings;
@@ -334,6 +521,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    ..Default::default()
                 }],
             };
             let merged = timings.merge(&other);
@@ -354,6 +542,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    ..Default::default()
                 }],
             };
             let merged = timings.merge(&other);
@@ -365,6 +554,30 @@ mod tests {
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_stored_memory_stats() {
+            let mut timings = get_mock_timings();
+            timings.data[0].part_1_memory = Some(MemoryStats {
+                peak_bytes: 1,
+                total_bytes: 2,
+                allocations: 3,
+            });
+
+            let other = Timings {
+                data: vec![Timing {
+                    day: day!(1),
+                    part_1: Some("5ms".into()),
+                    part_2: None,
+                    total_nanos: 0_f64,
+                    ..Default::default()
+                }],
+            };
+            let merged = timings.merge(&other);
+
+            assert_eq!(merged.data[0].part_1, Some("5ms".into()));
+            assert_eq!(merged.data[0].part_1_memory.unwrap().allocations, 3);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
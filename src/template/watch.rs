@@ -0,0 +1,87 @@
+/// Module that re-runs a command whenever watched files change.
+/// Changes are detected by polling modification times, so no file system notification tools are required.
+use std::{
+    fs,
+    io::{stdout, Write},
+    path::PathBuf,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{ANSI_ITALIC, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const ANSI_CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Modification times of a set of files, `None` for files that do not exist.
+#[derive(Debug, PartialEq, Eq)]
+struct Snapshot(Vec<(PathBuf, Option<SystemTime>)>);
+
+impl Snapshot {
+    fn take(paths: Vec<PathBuf>) -> Self {
+        Snapshot(
+            paths
+                .into_iter()
+                .map(|path| {
+                    let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+                    (path, modified)
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Run `run` and run it again whenever one of the files returned by `paths` changes. Never returns.
+pub fn watch(paths: impl Fn() -> Vec<PathBuf>, mut run: impl FnMut()) {
+    loop {
+        let snapshot = Snapshot::take(paths());
+
+        print!("{ANSI_CLEAR_SCREEN}");
+        let _ = stdout().flush();
+        run();
+
+        println!();
+        println!("{ANSI_ITALIC}Watching for changes, press Ctrl+C to stop.{ANSI_RESET}");
+
+        while Snapshot::take(paths()) == snapshot {
+            thread::sleep(POLL_INTERVAL);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::{
+        env,
+        fs::{self, File},
+        time::{Duration, SystemTime},
+    };
+
+    use super::Snapshot;
+
+    #[test]
+    fn detects_changes() {
+        let path = env::temp_dir().join(format!("aoc-watch-{}.txt", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let missing = Snapshot::take(vec![path.clone()]);
+        assert_eq!(missing, Snapshot::take(vec![path.clone()]));
+
+        fs::write(&path, "a").unwrap();
+        let created = Snapshot::take(vec![path.clone()]);
+        assert_ne!(missing, created);
+
+        fs::write(&path, "b").unwrap();
+        File::options()
+            .write(true)
+            .open(&path)
+            .and_then(|file| file.set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(60)))
+            .unwrap();
+        let modified = Snapshot::take(vec![path.clone()]);
+        fs::remove_file(&path).unwrap();
+
+        assert_ne!(created, modified);
+    }
+}